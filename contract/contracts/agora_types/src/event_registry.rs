@@ -0,0 +1,400 @@
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, Map, String, Vec};
+
+pub use crate::status::EventStatus;
+
+/// A time-bounded price that applies to a tier before its regular price.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricePhase {
+    /// Name of the phase (e.g., "Super Early", "Early Bird")
+    pub name: String,
+    /// Price during this phase in stroops
+    pub price: i128,
+    /// Last timestamp (inclusive) at which this phase applies
+    pub ends_at: u64,
+}
+
+/// One step of a tier's refund schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundWindow {
+    /// Applies while at least this many seconds remain before the event starts
+    pub min_seconds_before_start: u64,
+    /// Share of the ticket price refunded (in basis points, 10000 = 100%)
+    pub refund_bps: u32,
+}
+
+/// Time-based refund rules for a tier, e.g. 100% until 14 days before the
+/// event, 50% until 48 hours before, then nothing. A policy without windows
+/// refunds in full at any time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundPolicy {
+    /// Windows ordered by strictly decreasing `min_seconds_before_start`
+    pub windows: Vec<RefundWindow>,
+    /// Fixed fee in stroops withheld from every refund and kept by the organizer
+    pub processing_fee: i128,
+}
+
+impl RefundPolicy {
+    /// A policy that refunds the full price with no processing fee.
+    pub fn full_refund(env: &Env) -> Self {
+        RefundPolicy {
+            windows: Vec::new(env),
+            processing_fee: 0,
+        }
+    }
+
+    /// Returns the refund share in basis points when `seconds_before_start`
+    /// remain before the event, or 0 once every window has passed.
+    pub fn refund_bps(&self, seconds_before_start: u64) -> u32 {
+        if self.windows.is_empty() {
+            return 10000;
+        }
+        for window in self.windows.iter() {
+            if seconds_before_start >= window.min_seconds_before_start {
+                return window.refund_bps;
+            }
+        }
+        0
+    }
+}
+
+/// Represents a ticket tier with its own pricing and supply
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketTier {
+    /// Name of the tier (e.g., "General", "VIP", "Reserved")
+    pub name: String,
+    /// Regular price for this tier in stroops, charged once every phase has ended
+    pub price: i128,
+    /// Discounted phases ordered by `ends_at`, applied before the regular price
+    pub price_phases: Vec<PricePhase>,
+    /// Maximum tickets available for this tier
+    pub tier_limit: i128,
+    /// Current number of tickets sold for this tier
+    pub current_sold: i128,
+    /// Timestamp when sales open for this tier (0 = follow the event window)
+    pub sales_start_time: u64,
+    /// Timestamp when sales close for this tier (0 = follow the event window)
+    pub sales_end_time: u64,
+    /// Indicates whether tickets in this tier can be refunded by the buyer
+    pub is_refundable: bool,
+    /// Time-based refund schedule applied while `is_refundable` is set
+    pub refund_policy: RefundPolicy,
+    /// Retired tiers keep their sold tickets but accept no new purchases
+    pub is_retired: bool,
+}
+
+/// Represents an early revenue release milestone.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    /// The number of tickets sold to reach this milestone
+    pub sales_threshold: i128,
+    /// Percentage of the available revenue to release (in basis points, 10000 = 100%)
+    pub release_percent: u32,
+}
+
+/// One payee's share of an event's organizer revenue.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueSplit {
+    /// The address that can claim this share
+    pub payee: Address,
+    /// Share of the organizer revenue (in basis points, 10000 = 100%)
+    pub bps: u32,
+}
+
+impl TicketTier {
+    /// Resolves the price in effect at `now`.
+    ///
+    /// Returns the index of the active phase together with its price. Once every
+    /// phase has ended the index equals `price_phases.len()` and the regular
+    /// `price` applies.
+    pub fn active_price(&self, now: u64) -> (u32, i128) {
+        for (index, phase) in self.price_phases.iter().enumerate() {
+            if now <= phase.ends_at {
+                return (index as u32, phase.price);
+            }
+        }
+        (self.price_phases.len(), self.price)
+    }
+}
+
+/// Represents information about an event in the registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventInfo {
+    /// Unique identifier for the event
+    pub event_id: String,
+    /// The wallet address of the event organizer
+    pub organizer_address: Address,
+    /// The address where payments for this event should be routed
+    pub payment_address: Address,
+    /// The platform fee in basis points, from the organizer's fee plan or the
+    /// global fee at registration, unless the administrator overrides it
+    pub platform_fee_percent: u32,
+    /// Current lifecycle status of the event
+    pub status: EventStatus,
+    /// Timestamp when the event was created
+    pub created_at: u64,
+    /// IPFS Content Identifier storing rich metadata details
+    pub metadata_cid: String,
+    /// Maximum number of tickets available for this event (0 = unlimited)
+    pub max_supply: i128,
+    /// Current number of tickets that have been successfully purchased
+    pub current_supply: i128,
+    /// Timestamp when the event starts (0 = not scheduled)
+    pub start_time: u64,
+    /// Timestamp when the event ends (0 = not scheduled)
+    pub end_time: u64,
+    /// Timestamp when ticket sales open (0 = open on registration)
+    pub sales_start_time: u64,
+    /// Timestamp when ticket sales close (0 = close at `end_time`, if set)
+    pub sales_end_time: u64,
+    /// Optional milestone plan for early revenue release
+    pub milestone_plan: Option<Vec<Milestone>>,
+    /// Optional split of organizer revenue between payees; without one the
+    /// organizer receives everything
+    pub revenue_splits: Option<Vec<RevenueSplit>>,
+    /// Tickets that must sell by `sales_target_deadline` for the event to go
+    /// ahead (0 = no target)
+    pub min_sales_target: i128,
+    /// Timestamp by which `min_sales_target` must be reached (0 = no target)
+    pub sales_target_deadline: u64,
+    /// Set once the deadline has passed with `min_sales_target` reached; stays
+    /// set if tickets are refunded afterwards
+    pub sales_target_met: bool,
+    /// Timestamp when the event was marked `Completed` (0 = not completed)
+    pub completed_at: u64,
+    /// Map of tier_id to TicketTier for multi-tiered pricing
+    pub tiers: Map<String, TicketTier>,
+}
+
+impl EventInfo {
+    /// Returns true while the event has a sales target it has not met yet.
+    /// The target is only judged at its deadline, since tickets sold before
+    /// then may still expire or be refunded. Its revenue stays in escrow until
+    /// then.
+    pub fn sales_target_pending(&self, now: u64) -> bool {
+        self.min_sales_target > 0
+            && !self.sales_target_met
+            && (now < self.sales_target_deadline || self.current_supply < self.min_sales_target)
+    }
+
+    /// Returns true once the sales target deadline has passed without the
+    /// target being met. Such an event will not go ahead and every ticket is
+    /// refundable in full, whether or not it has been marked cancelled yet.
+    pub fn sales_target_missed(&self, now: u64) -> bool {
+        self.sales_target_pending(now) && now >= self.sales_target_deadline
+    }
+}
+
+/// Payment information for an event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentInfo {
+    /// The address where payments for this event should be routed
+    pub payment_address: Address,
+    /// The percentage fee taken by the platform
+    pub platform_fee_percent: u32,
+    /// Map of tier_id to TicketTier for multi-tiered pricing
+    pub tiers: Map<String, TicketTier>,
+}
+
+/// Staff role an organizer can delegate for a single event.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventRole {
+    /// May check tickets in at the door
+    Scanner,
+    /// May start refunds on the organizer's behalf
+    Finance,
+    /// May edit the event's metadata
+    Manager,
+}
+
+/// Arguments required to register a new event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventRegistrationArgs {
+    pub event_id: String,
+    pub organizer_address: Address,
+    pub payment_address: Address,
+    pub metadata_cid: String,
+    pub max_supply: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub sales_start_time: u64,
+    pub sales_end_time: u64,
+    pub milestone_plan: Option<Vec<Milestone>>,
+    pub revenue_splits: Option<Vec<RevenueSplit>>,
+    pub min_sales_target: i128,
+    pub sales_target_deadline: u64,
+    /// Registers the event as `Draft`, hidden from buyers until the organizer
+    /// moves it to `OnSale`
+    pub draft: bool,
+    pub tiers: Map<String, TicketTier>,
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EventRegistryError {
+    EventAlreadyExists = 1,
+    EventNotFound = 2,
+    Unauthorized = 3,
+    InvalidAddress = 4,
+    InvalidFeePercent = 5,
+    EventInactive = 6,
+    NotInitialized = 7,
+    AlreadyInitialized = 8,
+    InvalidMetadataCid = 9,
+    MaxSupplyExceeded = 10,
+    SupplyOverflow = 11,
+    UnauthorizedCaller = 12,
+    TierLimitExceedsMaxSupply = 13,
+    TierNotFound = 14,
+    TierSupplyExceeded = 15,
+    SupplyUnderflow = 16,
+    InvalidQuantity = 17,
+    InvalidStatusTransition = 18,
+    InvalidSchedule = 19,
+    SalesNotStarted = 20,
+    SalesEnded = 21,
+    TierAlreadyExists = 22,
+    TierRetired = 23,
+    InvalidTierConfig = 24,
+    TierLimitBelowSold = 25,
+    InvalidPriceSchedule = 26,
+    InvalidRefundPolicy = 27,
+    InvalidRevenueSplit = 28,
+    FeePlanNotFound = 29,
+    InvalidSalesTarget = 30,
+    SalesTargetNotMissed = 31,
+}
+
+impl core::fmt::Display for EventRegistryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EventRegistryError::EventAlreadyExists => write!(f, "Event already exists"),
+            EventRegistryError::EventNotFound => write!(f, "Event not found"),
+            EventRegistryError::Unauthorized => write!(f, "Caller not authorized for action"),
+            EventRegistryError::InvalidAddress => write!(f, "Invalid Stellar address"),
+            EventRegistryError::InvalidFeePercent => {
+                write!(f, "Fee percent must be between 0 and 10000")
+            }
+            EventRegistryError::EventInactive => {
+                write!(f, "Trying to interact with inactive event")
+            }
+            EventRegistryError::NotInitialized => write!(f, "Contract not initialized"),
+            EventRegistryError::AlreadyInitialized => write!(f, "Contract already initialized"),
+            EventRegistryError::InvalidMetadataCid => write!(f, "Invalid IPFS Metadata CID format"),
+            EventRegistryError::MaxSupplyExceeded => {
+                write!(f, "Event has reached its maximum ticket supply")
+            }
+            EventRegistryError::SupplyOverflow => {
+                write!(f, "Supply counter overflow")
+            }
+            EventRegistryError::UnauthorizedCaller => {
+                write!(f, "Caller is not the authorized TicketPayment contract")
+            }
+            EventRegistryError::TierLimitExceedsMaxSupply => {
+                write!(f, "Sum of tier limits exceeds event max supply")
+            }
+            EventRegistryError::TierNotFound => {
+                write!(f, "Ticket tier not found")
+            }
+            EventRegistryError::TierSupplyExceeded => {
+                write!(f, "Tier has reached its maximum supply")
+            }
+            EventRegistryError::SupplyUnderflow => {
+                write!(f, "Supply counter underflow")
+            }
+            EventRegistryError::InvalidQuantity => {
+                write!(f, "Quantity must be greater than zero")
+            }
+            EventRegistryError::InvalidStatusTransition => {
+                write!(f, "Event status transition is not allowed")
+            }
+            EventRegistryError::InvalidSchedule => {
+                write!(f, "Event schedule or sales window is inconsistent")
+            }
+            EventRegistryError::SalesNotStarted => {
+                write!(f, "Ticket sales have not opened yet")
+            }
+            EventRegistryError::SalesEnded => write!(f, "Ticket sales have closed"),
+            EventRegistryError::TierAlreadyExists => write!(f, "Ticket tier already exists"),
+            EventRegistryError::TierRetired => write!(f, "Ticket tier has been retired"),
+            EventRegistryError::InvalidTierConfig => {
+                write!(f, "Tier price and limit must not be negative")
+            }
+            EventRegistryError::TierLimitBelowSold => {
+                write!(f, "Tier limit cannot drop below tickets already sold")
+            }
+            EventRegistryError::InvalidPriceSchedule => {
+                write!(
+                    f,
+                    "Price phases must have non-negative prices and increasing end times"
+                )
+            }
+            EventRegistryError::InvalidRefundPolicy => {
+                write!(
+                    f,
+                    "Refund windows must be ordered and within 100%, with a non-negative fee"
+                )
+            }
+            EventRegistryError::InvalidRevenueSplit => {
+                write!(
+                    f,
+                    "Revenue splits must list distinct payees with shares adding up to 100%"
+                )
+            }
+            EventRegistryError::FeePlanNotFound => write!(f, "Fee plan not found"),
+            EventRegistryError::InvalidSalesTarget => write!(
+                f,
+                "Sales target must be positive, fit in max supply and have a deadline"
+            ),
+            EventRegistryError::SalesTargetNotMissed => {
+                write!(f, "Event has no sales target that was missed")
+            }
+        }
+    }
+}
+
+/// Entrypoints of the Event Registry that other contracts call.
+///
+/// The registry implements this trait directly, so a signature change on
+/// either side fails to compile instead of failing at invocation time.
+#[contractclient(name = "Client")]
+pub trait EventRegistryInterface {
+    /// Get event payment information including tiered pricing
+    fn get_event_payment_info(
+        env: Env,
+        event_id: String,
+    ) -> Result<PaymentInfo, EventRegistryError>;
+
+    /// Retrieves an event by its ID.
+    fn get_event(env: Env, event_id: String) -> Option<EventInfo>;
+
+    /// Records `quantity` tickets sold for a tier. Restricted to the
+    /// registered TicketPayment contract.
+    fn increment_inventory(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        quantity: u32,
+    ) -> Result<(), EventRegistryError>;
+
+    /// Releases one sold ticket for a tier after a refund. Restricted to the
+    /// registered TicketPayment contract.
+    fn decrement_inventory(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Result<(), EventRegistryError>;
+
+    /// Returns true if the organizer granted `role` on the event to `account`.
+    fn has_role(env: Env, event_id: String, role: EventRole, account: Address) -> bool;
+}
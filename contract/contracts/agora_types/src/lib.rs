@@ -0,0 +1,22 @@
+#![no_std]
+
+//! Types shared by the Agora contracts and the off-chain server.
+//!
+//! The `soroban` feature (enabled by default) exposes the Event Registry's
+//! contract types, its error enum and the client `ticket_payment` uses to call
+//! it. The status enums in [`status`] are plain Rust so the server can use them
+//! without pulling in the Soroban SDK; enable `serde` to (de)serialize them
+//! and `alloc` to convert them from owned strings, such as database columns.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod status;
+
+#[cfg(feature = "soroban")]
+pub mod event_registry;
+
+#[cfg(feature = "testutils")]
+pub mod testutils;
+
+pub use status::{EventStatus, PaymentStatus, UnknownStatus};
@@ -0,0 +1,146 @@
+/// Lifecycle status of an event.
+///
+/// Allowed transitions:
+/// * `Draft` -> `OnSale` | `Cancelled`
+/// * `OnSale` -> `Paused` | `SalesClosed` | `Cancelled`
+/// * `Paused` -> `OnSale` | `SalesClosed` | `Cancelled`
+/// * `SalesClosed` -> `OnSale` | `Completed` | `Cancelled`
+/// * `Cancelled` and `Completed` are terminal.
+#[cfg_attr(feature = "soroban", soroban_sdk::contracttype)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventStatus {
+    /// Registered but not yet visible to buyers
+    Draft,
+    /// Accepting ticket purchases
+    OnSale,
+    /// Sales temporarily halted by the organizer; tickets keep their refund rules
+    Paused,
+    /// Sales have ended but the event has not taken place yet
+    SalesClosed,
+    /// The event will not take place; every ticket is refundable
+    Cancelled,
+    /// The event has taken place
+    Completed,
+}
+
+impl EventStatus {
+    /// Returns true if the lifecycle allows moving from `self` to `next`.
+    pub fn can_transition_to(&self, next: EventStatus) -> bool {
+        matches!(
+            (self, next),
+            (EventStatus::Draft, EventStatus::OnSale)
+                | (EventStatus::Draft, EventStatus::Cancelled)
+                | (EventStatus::OnSale, EventStatus::Paused)
+                | (EventStatus::OnSale, EventStatus::SalesClosed)
+                | (EventStatus::OnSale, EventStatus::Cancelled)
+                | (EventStatus::Paused, EventStatus::OnSale)
+                | (EventStatus::Paused, EventStatus::SalesClosed)
+                | (EventStatus::Paused, EventStatus::Cancelled)
+                | (EventStatus::SalesClosed, EventStatus::OnSale)
+                | (EventStatus::SalesClosed, EventStatus::Completed)
+                | (EventStatus::SalesClosed, EventStatus::Cancelled)
+        )
+    }
+}
+
+/// Settlement status of a ticket payment.
+#[cfg_attr(feature = "soroban", soroban_sdk::contracttype)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PaymentStatus {
+    Pending,
+    Confirmed,
+    Refunded,
+    Failed,
+    /// Scanned at the door; the ticket can no longer be transferred or refunded
+    CheckedIn,
+}
+
+/// Error returned when parsing a status name that matches no variant.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnknownStatus;
+
+impl core::fmt::Display for UnknownStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Unknown status")
+    }
+}
+
+impl EventStatus {
+    /// Returns the snake_case name also used by the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventStatus::Draft => "draft",
+            EventStatus::OnSale => "on_sale",
+            EventStatus::Paused => "paused",
+            EventStatus::SalesClosed => "sales_closed",
+            EventStatus::Cancelled => "cancelled",
+            EventStatus::Completed => "completed",
+        }
+    }
+}
+
+impl core::str::FromStr for EventStatus {
+    type Err = UnknownStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(EventStatus::Draft),
+            "on_sale" => Ok(EventStatus::OnSale),
+            "paused" => Ok(EventStatus::Paused),
+            "sales_closed" => Ok(EventStatus::SalesClosed),
+            "cancelled" => Ok(EventStatus::Cancelled),
+            "completed" => Ok(EventStatus::Completed),
+            _ => Err(UnknownStatus),
+        }
+    }
+}
+
+impl PaymentStatus {
+    /// Returns the snake_case name also used by the serde representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Confirmed => "confirmed",
+            PaymentStatus::Refunded => "refunded",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::CheckedIn => "checked_in",
+        }
+    }
+}
+
+impl core::str::FromStr for PaymentStatus {
+    type Err = UnknownStatus;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(PaymentStatus::Pending),
+            "confirmed" => Ok(PaymentStatus::Confirmed),
+            "refunded" => Ok(PaymentStatus::Refunded),
+            "failed" => Ok(PaymentStatus::Failed),
+            "checked_in" => Ok(PaymentStatus::CheckedIn),
+            _ => Err(UnknownStatus),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<alloc::string::String> for EventStatus {
+    type Error = UnknownStatus;
+
+    fn try_from(s: alloc::string::String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<alloc::string::String> for PaymentStatus {
+    type Error = UnknownStatus;
+
+    fn try_from(s: alloc::string::String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
@@ -0,0 +1,39 @@
+//! Helpers for the contracts' randomized tests.
+
+use soroban_sdk::{Env, IntoVal, String, TryFromVal, Val, Vec};
+
+/// A xorshift generator, so a failing run can be replayed from its seed.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn below(&mut self, n: u32) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as u32
+    }
+
+    pub fn pick<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(
+        &mut self,
+        items: &Vec<T>,
+    ) -> Option<T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.below(items.len()))
+        }
+    }
+}
+
+/// Builds IDs such as `e7` or `t12`.
+pub fn id(env: &Env, prefix: u8, n: u32) -> String {
+    let mut buf = [prefix, 0, 0, 0];
+    let mut len = 1;
+    for divisor in [100, 10, 1] {
+        if n >= divisor || divisor == 1 {
+            buf[len] = b'0' + (n / divisor % 10) as u8;
+            len += 1;
+        }
+    }
+    String::from_bytes(env, &buf[..len])
+}
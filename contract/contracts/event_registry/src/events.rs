@@ -1,3 +1,4 @@
+use crate::types::{EventRole, EventStatus};
 use soroban_sdk::{contracttype, Address, String};
 
 #[contracttype]
@@ -11,6 +12,15 @@ pub enum AgoraEvent {
     MetadataUpdated,
     InventoryIncremented,
     InventoryDecremented,
+    TierAdded,
+    TierUpdated,
+    TierRetired,
+    RoleGranted,
+    RoleRevoked,
+    FeePlanUpdated,
+    OrganizerFeePlanUpdated,
+    EventFeeOverridden,
+    SalesTargetMet,
 }
 
 #[contracttype]
@@ -26,7 +36,8 @@ pub struct EventRegisteredEvent {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EventStatusUpdatedEvent {
     pub event_id: String,
-    pub is_active: bool,
+    pub old_status: EventStatus,
+    pub new_status: EventStatus,
     pub updated_by: Address,
     pub timestamp: u64,
 }
@@ -79,3 +90,73 @@ pub struct InventoryDecrementedEvent {
     pub max_supply: i128,
     pub timestamp: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierAddedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub price: i128,
+    pub tier_limit: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierUpdatedEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub old_price: i128,
+    pub new_price: i128,
+    pub old_limit: i128,
+    pub new_limit: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierRetiredEvent {
+    pub event_id: String,
+    pub tier_id: String,
+    pub final_sold: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleUpdatedEvent {
+    pub event_id: String,
+    pub role: EventRole,
+    pub account: Address,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeePlanUpdatedEvent {
+    pub plan_id: String,
+    pub fee_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrganizerFeePlanUpdatedEvent {
+    pub organizer_address: Address,
+    pub plan_id: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventFeeOverriddenEvent {
+    pub event_id: String,
+    pub fee_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalesTargetMetEvent {
+    pub event_id: String,
+    pub min_sales_target: i128,
+    pub timestamp: u64,
+}
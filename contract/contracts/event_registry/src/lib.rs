@@ -1,11 +1,17 @@
 #![no_std]
 
 use crate::events::{
-    AgoraEvent, EventRegisteredEvent, EventStatusUpdatedEvent, FeeUpdatedEvent,
-    InitializationEvent, InventoryIncrementedEvent, MetadataUpdatedEvent, RegistryUpgradedEvent,
+    AgoraEvent, EventFeeOverriddenEvent, EventRegisteredEvent, EventStatusUpdatedEvent,
+    FeePlanUpdatedEvent, FeeUpdatedEvent, InitializationEvent, InventoryIncrementedEvent,
+    MetadataUpdatedEvent, OrganizerFeePlanUpdatedEvent, RegistryUpgradedEvent, RoleUpdatedEvent,
+    SalesTargetMetEvent, TierAddedEvent, TierRetiredEvent, TierUpdatedEvent,
 };
-use crate::types::{EventInfo, EventRegistrationArgs, PaymentInfo};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+use crate::types::{
+    EventInfo, EventRegistrationArgs, EventRole, EventStatus, PaymentInfo, PricePhase,
+    RevenueSplit, TicketTier,
+};
+use agora_types::event_registry::EventRegistryInterface;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Vec};
 
 pub mod error;
 pub mod events;
@@ -73,6 +79,8 @@ impl EventRegistry {
     /// * `payment_address` - The address where payments should be routed
     /// * `metadata_cid` - IPFS CID for event metadata
     /// * `max_supply` - Maximum number of tickets (0 = unlimited)
+    /// * `revenue_splits` - Optional payees sharing the organizer revenue
+    /// * `draft` - Register as `Draft` and publish later instead of going on sale
     /// * `tiers` - Map of tier_id to TicketTier for multi-tiered pricing
     pub fn register_event(env: Env, args: EventRegistrationArgs) -> Result<(), EventRegistryError> {
         if !storage::is_initialized(&env) {
@@ -81,38 +89,60 @@ impl EventRegistry {
         args.organizer_address.require_auth();
 
         validate_metadata_cid(&env, &args.metadata_cid)?;
+        validate_schedule(&args)?;
+        if let Some(splits) = &args.revenue_splits {
+            validate_revenue_splits(splits)?;
+        }
+        validate_sales_target(&args)?;
 
         if storage::event_exists(&env, args.event_id.clone()) {
             return Err(EventRegistryError::EventAlreadyExists);
         }
 
         // Validate tier limits don't exceed max_supply
-        if args.max_supply > 0 {
-            let mut total_tier_limit: i128 = 0;
-            for tier in args.tiers.values() {
-                total_tier_limit = total_tier_limit
-                    .checked_add(tier.tier_limit)
-                    .ok_or(EventRegistryError::SupplyOverflow)?;
-            }
-            if total_tier_limit > args.max_supply {
-                return Err(EventRegistryError::TierLimitExceedsMaxSupply);
-            }
+        validate_tier_limits(&args.tiers, args.max_supply)?;
+
+        // Tickets are only sold through `increment_inventory`, so every tier
+        // starts empty whatever the caller passed
+        let mut tiers = Map::new(&env);
+        for (tier_id, tier) in args.tiers.iter() {
+            tiers.set(
+                tier_id,
+                TicketTier {
+                    current_sold: 0,
+                    is_retired: false,
+                    ..tier
+                },
+            );
         }
 
-        let platform_fee_percent = storage::get_platform_fee(&env);
+        let platform_fee_percent = organizer_fee(&env, &args.organizer_address);
 
         let event_info = EventInfo {
             event_id: args.event_id.clone(),
             organizer_address: args.organizer_address.clone(),
             payment_address: args.payment_address.clone(),
             platform_fee_percent,
-            is_active: true,
+            status: if args.draft {
+                EventStatus::Draft
+            } else {
+                EventStatus::OnSale
+            },
             created_at: env.ledger().timestamp(),
             metadata_cid: args.metadata_cid.clone(),
             max_supply: args.max_supply,
             current_supply: 0,
+            start_time: args.start_time,
+            end_time: args.end_time,
+            sales_start_time: args.sales_start_time,
+            sales_end_time: args.sales_end_time,
             milestone_plan: args.milestone_plan.clone(),
-            tiers: args.tiers.clone(),
+            revenue_splits: args.revenue_splits.clone(),
+            min_sales_target: args.min_sales_target,
+            sales_target_deadline: args.sales_target_deadline,
+            sales_target_met: false,
+            completed_at: 0,
+            tiers,
         };
 
         storage::store_event(&env, event_info);
@@ -130,39 +160,32 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Get event payment information including tiered pricing
-    pub fn get_event_payment_info(
-        env: Env,
-        event_id: String,
-    ) -> Result<PaymentInfo, EventRegistryError> {
-        match storage::get_event(&env, event_id) {
-            Some(event_info) => {
-                if !event_info.is_active {
-                    return Err(EventRegistryError::EventInactive);
-                }
-                Ok(PaymentInfo {
-                    payment_address: event_info.payment_address,
-                    platform_fee_percent: event_info.platform_fee_percent,
-                    tiers: event_info.tiers,
-                })
-            }
-            None => Err(EventRegistryError::EventNotFound),
-        }
-    }
-
-    /// Update event status (only by organizer)
+    /// Moves an event to a new lifecycle status (only by organizer).
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `InvalidStatusTransition` - If the lifecycle does not allow moving from the
+    ///   current status to `new_status`.
     pub fn update_event_status(
         env: Env,
         event_id: String,
-        is_active: bool,
+        new_status: EventStatus,
     ) -> Result<(), EventRegistryError> {
         match storage::get_event(&env, event_id.clone()) {
             Some(mut event_info) => {
                 // Verify organizer signature
                 event_info.organizer_address.require_auth();
 
+                let old_status = event_info.status;
+                if !old_status.can_transition_to(new_status) {
+                    return Err(EventRegistryError::InvalidStatusTransition);
+                }
+
                 // Update status
-                event_info.is_active = is_active;
+                event_info.status = new_status;
+                if new_status == EventStatus::Completed {
+                    event_info.completed_at = env.ledger().timestamp();
+                }
                 storage::store_event(&env, event_info.clone());
 
                 // Emit status update event using contract event type
@@ -170,7 +193,8 @@ impl EventRegistry {
                     (AgoraEvent::EventStatusUpdated,),
                     EventStatusUpdatedEvent {
                         event_id,
-                        is_active,
+                        old_status,
+                        new_status,
                         updated_by: event_info.organizer_address,
                         timestamp: env.ledger().timestamp(),
                     },
@@ -182,16 +206,65 @@ impl EventRegistry {
         }
     }
 
-    /// Update the decentralized metadata CID for an event (only by organizer)
+    /// Cancels an event whose sales target deadline has passed without the
+    /// target being met. Anyone can call this; the event's buyers can then
+    /// claim full refunds from `ticket_payment`.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `SalesTargetNotMissed` - If the event has no missed sales target.
+    /// * `InvalidStatusTransition` - If the event is already cancelled or completed.
+    pub fn cancel_unmet_event(env: Env, event_id: String) -> Result<(), EventRegistryError> {
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+
+        if !event_info.sales_target_missed(env.ledger().timestamp()) {
+            return Err(EventRegistryError::SalesTargetNotMissed);
+        }
+        let old_status = event_info.status;
+        if !old_status.can_transition_to(EventStatus::Cancelled) {
+            return Err(EventRegistryError::InvalidStatusTransition);
+        }
+
+        event_info.status = EventStatus::Cancelled;
+        storage::store_event(&env, event_info);
+
+        env.events().publish(
+            (AgoraEvent::EventStatusUpdated,),
+            EventStatusUpdatedEvent {
+                event_id,
+                old_status,
+                new_status: EventStatus::Cancelled,
+                updated_by: env.current_contract_address(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Update the decentralized metadata CID for an event (by the organizer or
+    /// an account holding the `Manager` role)
     pub fn update_metadata(
         env: Env,
         event_id: String,
         new_metadata_cid: String,
+        caller: Address,
     ) -> Result<(), EventRegistryError> {
         match storage::get_event(&env, event_id.clone()) {
             Some(mut event_info) => {
-                // Verify organizer signature
-                event_info.organizer_address.require_auth();
+                // Verify caller signature and delegation
+                caller.require_auth();
+                if caller != event_info.organizer_address
+                    && !storage::has_role(
+                        &env,
+                        event_id.clone(),
+                        EventRole::Manager,
+                        caller.clone(),
+                    )
+                {
+                    return Err(EventRegistryError::Unauthorized);
+                }
 
                 // Validate new metadata CID
                 validate_metadata_cid(&env, &new_metadata_cid)?;
@@ -206,7 +279,7 @@ impl EventRegistry {
                     MetadataUpdatedEvent {
                         event_id,
                         new_metadata_cid,
-                        updated_by: event_info.organizer_address,
+                        updated_by: caller,
                         timestamp: env.ledger().timestamp(),
                     },
                 );
@@ -217,17 +290,300 @@ impl EventRegistry {
         }
     }
 
+    /// Adds a new ticket tier to an existing event (only by organizer).
+    ///
+    /// The tier starts with no tickets sold, and the sum of all tier limits must
+    /// still fit within the event's `max_supply`.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `EventInactive` - If the event is cancelled or completed.
+    /// * `TierAlreadyExists` - If `tier_id` is already used by this event.
+    /// * `InvalidTierConfig` - If the price or limit is negative.
+    /// * `InvalidSchedule` - If the tier sales window is inverted or falls outside
+    ///   the event's sales window.
+    /// * `InvalidPriceSchedule` - If the price phases are unordered or negative.
+    /// * `TierLimitExceedsMaxSupply` - If the new tier does not fit in `max_supply`.
+    pub fn add_tier(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        tier: TicketTier,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info = get_manageable_event(&env, event_id.clone())?;
+        event_info.organizer_address.require_auth();
+
+        if event_info.tiers.contains_key(tier_id.clone()) {
+            return Err(EventRegistryError::TierAlreadyExists);
+        }
+        if tier.price < 0 || tier.tier_limit < 0 {
+            return Err(EventRegistryError::InvalidTierConfig);
+        }
+        validate_tier_window(
+            &tier,
+            event_info.sales_start_time,
+            event_sales_end(event_info.sales_end_time, event_info.end_time),
+        )?;
+        validate_price_phases(&tier)?;
+        validate_refund_policy(&tier)?;
+
+        let tier = TicketTier {
+            current_sold: 0,
+            is_retired: false,
+            ..tier
+        };
+        event_info.tiers.set(tier_id.clone(), tier.clone());
+        validate_tier_limits(&event_info.tiers, event_info.max_supply)?;
+
+        storage::store_event(&env, event_info);
+
+        env.events().publish(
+            (AgoraEvent::TierAdded,),
+            TierAddedEvent {
+                event_id,
+                tier_id,
+                price: tier.price,
+                tier_limit: tier.tier_limit,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Changes the regular price and limit of an existing tier (only by organizer).
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `EventInactive` - If the event is cancelled or completed.
+    /// * `TierNotFound` - If the tier does not exist.
+    /// * `TierRetired` - If the tier has been retired.
+    /// * `InvalidTierConfig` - If the price or limit is negative.
+    /// * `TierLimitBelowSold` - If `new_limit` is below the tickets already sold.
+    /// * `TierLimitExceedsMaxSupply` - If the new limit does not fit in `max_supply`.
+    pub fn update_tier(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        new_price: i128,
+        new_limit: i128,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info = get_manageable_event(&env, event_id.clone())?;
+        event_info.organizer_address.require_auth();
+
+        let mut tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+
+        if tier.is_retired {
+            return Err(EventRegistryError::TierRetired);
+        }
+        if new_price < 0 || new_limit < 0 {
+            return Err(EventRegistryError::InvalidTierConfig);
+        }
+        if new_limit < tier.current_sold {
+            return Err(EventRegistryError::TierLimitBelowSold);
+        }
+
+        let old_price = tier.price;
+        let old_limit = tier.tier_limit;
+        tier.price = new_price;
+        tier.tier_limit = new_limit;
+        event_info.tiers.set(tier_id.clone(), tier);
+        validate_tier_limits(&event_info.tiers, event_info.max_supply)?;
+
+        storage::store_event(&env, event_info);
+
+        env.events().publish(
+            (AgoraEvent::TierUpdated,),
+            TierUpdatedEvent {
+                event_id,
+                tier_id,
+                old_price,
+                new_price,
+                old_limit,
+                new_limit,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Replaces the price phases of an existing tier (only by organizer).
+    ///
+    /// Emits `TierUpdated` with the price in effect before and after the change;
+    /// the tier limit is unchanged. Tickets already sold keep the price paid.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `EventInactive` - If the event is cancelled or completed.
+    /// * `TierNotFound` - If the tier does not exist.
+    /// * `TierRetired` - If the tier has been retired.
+    /// * `InvalidPriceSchedule` - If the price phases are unordered or negative.
+    pub fn update_price_phases(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+        price_phases: Vec<PricePhase>,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info = get_manageable_event(&env, event_id.clone())?;
+        event_info.organizer_address.require_auth();
+
+        let mut tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+
+        if tier.is_retired {
+            return Err(EventRegistryError::TierRetired);
+        }
+
+        let now = env.ledger().timestamp();
+        let (_, old_price) = tier.active_price(now);
+        tier.price_phases = price_phases;
+        validate_price_phases(&tier)?;
+        let (_, new_price) = tier.active_price(now);
+        let limit = tier.tier_limit;
+        event_info.tiers.set(tier_id.clone(), tier);
+
+        storage::store_event(&env, event_info);
+
+        env.events().publish(
+            (AgoraEvent::TierUpdated,),
+            TierUpdatedEvent {
+                event_id,
+                tier_id,
+                old_price,
+                new_price,
+                old_limit: limit,
+                new_limit: limit,
+                timestamp: now,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Retires a tier so it accepts no further purchases (only by organizer).
+    ///
+    /// Tickets already sold stay valid and refundable under the tier's rules. The
+    /// tier limit shrinks to the tickets sold, releasing the unsold capacity of
+    /// `max_supply` for other tiers.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    /// * `EventInactive` - If the event is cancelled or completed.
+    /// * `TierNotFound` - If the tier does not exist.
+    /// * `TierRetired` - If the tier has already been retired.
+    pub fn retire_tier(
+        env: Env,
+        event_id: String,
+        tier_id: String,
+    ) -> Result<(), EventRegistryError> {
+        let mut event_info = get_manageable_event(&env, event_id.clone())?;
+        event_info.organizer_address.require_auth();
+
+        let mut tier = event_info
+            .tiers
+            .get(tier_id.clone())
+            .ok_or(EventRegistryError::TierNotFound)?;
+
+        if tier.is_retired {
+            return Err(EventRegistryError::TierRetired);
+        }
+
+        tier.is_retired = true;
+        tier.tier_limit = tier.current_sold;
+        let final_sold = tier.current_sold;
+        event_info.tiers.set(tier_id.clone(), tier);
+
+        storage::store_event(&env, event_info);
+
+        env.events().publish(
+            (AgoraEvent::TierRetired,),
+            TierRetiredEvent {
+                event_id,
+                tier_id,
+                final_sold,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Delegates a staff role on an event to `account` (only by organizer).
+    ///
+    /// Roles let staff act for the organizer without holding its key: a
+    /// `Scanner` checks tickets in, `Finance` starts refunds and a `Manager`
+    /// edits metadata.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    pub fn grant_role(
+        env: Env,
+        event_id: String,
+        role: EventRole,
+        account: Address,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        event_info.organizer_address.require_auth();
+
+        storage::grant_role(&env, event_id.clone(), role, account.clone());
+
+        env.events().publish(
+            (AgoraEvent::RoleGranted,),
+            RoleUpdatedEvent {
+                event_id,
+                role,
+                account,
+                updated_by: event_info.organizer_address,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraws a staff role on an event from `account` (only by organizer).
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    pub fn revoke_role(
+        env: Env,
+        event_id: String,
+        role: EventRole,
+        account: Address,
+    ) -> Result<(), EventRegistryError> {
+        let event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        event_info.organizer_address.require_auth();
+
+        storage::revoke_role(&env, event_id.clone(), role, account.clone());
+
+        env.events().publish(
+            (AgoraEvent::RoleRevoked,),
+            RoleUpdatedEvent {
+                event_id,
+                role,
+                account,
+                updated_by: event_info.organizer_address,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
     /// Stores or updates an event (legacy function for backward compatibility).
     pub fn store_event(env: Env, event_info: EventInfo) {
         // In a real scenario, we would check authorization here.
         storage::store_event(&env, event_info);
     }
 
-    /// Retrieves an event by its ID.
-    pub fn get_event(env: Env, event_id: String) -> Option<EventInfo> {
-        storage::get_event(&env, event_id)
-    }
-
     /// Checks if an event exists.
     pub fn event_exists(env: Env, event_id: String) -> bool {
         storage::event_exists(&env, event_id)
@@ -263,6 +619,109 @@ impl EventRegistry {
         storage::get_platform_fee(&env)
     }
 
+    /// Creates or updates a fee plan. Only callable by the administrator.
+    ///
+    /// Organizers on the plan pay `fee_bps` instead of the global platform fee
+    /// on events they register from then on.
+    pub fn set_fee_plan(env: Env, plan_id: String, fee_bps: u32) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        if fee_bps > 10000 {
+            return Err(EventRegistryError::InvalidFeePercent);
+        }
+
+        storage::set_fee_plan(&env, plan_id.clone(), fee_bps);
+
+        env.events().publish(
+            (AgoraEvent::FeePlanUpdated,),
+            FeePlanUpdatedEvent { plan_id, fee_bps },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the platform fee of a fee plan, if it exists.
+    pub fn get_fee_plan(env: Env, plan_id: String) -> Option<u32> {
+        storage::get_fee_plan(&env, plan_id)
+    }
+
+    /// Puts an organizer on a fee plan, or back on the global platform fee when
+    /// `plan_id` is `None`. Only callable by the administrator.
+    ///
+    /// # Errors
+    /// * `FeePlanNotFound` - If the plan does not exist.
+    pub fn assign_fee_plan(
+        env: Env,
+        organizer: Address,
+        plan_id: Option<String>,
+    ) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        match plan_id.clone() {
+            Some(plan_id) => {
+                if storage::get_fee_plan(&env, plan_id.clone()).is_none() {
+                    return Err(EventRegistryError::FeePlanNotFound);
+                }
+                storage::set_organizer_fee_plan(&env, &organizer, plan_id);
+            }
+            None => storage::remove_organizer_fee_plan(&env, &organizer),
+        }
+
+        env.events().publish(
+            (AgoraEvent::OrganizerFeePlanUpdated,),
+            OrganizerFeePlanUpdatedEvent {
+                organizer_address: organizer,
+                plan_id,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the fee plan assigned to an organizer, if any.
+    pub fn get_organizer_fee_plan(env: Env, organizer: Address) -> Option<String> {
+        storage::get_organizer_fee_plan(&env, &organizer)
+    }
+
+    /// Returns the platform fee an organizer's new events are registered with.
+    pub fn get_organizer_fee(env: Env, organizer: Address) -> u32 {
+        organizer_fee(&env, &organizer)
+    }
+
+    /// Overrides the platform fee of a single event. Only callable by the
+    /// administrator.
+    ///
+    /// Tickets already sold keep the fee they were bought with.
+    ///
+    /// # Errors
+    /// * `EventNotFound` - If no event with the given ID exists.
+    pub fn set_event_fee(
+        env: Env,
+        event_id: String,
+        fee_bps: u32,
+    ) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        if fee_bps > 10000 {
+            return Err(EventRegistryError::InvalidFeePercent);
+        }
+
+        let mut event_info =
+            storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        event_info.platform_fee_percent = fee_bps;
+        storage::store_event(&env, event_info);
+
+        env.events().publish(
+            (AgoraEvent::EventFeeOverridden,),
+            EventFeeOverriddenEvent { event_id, fee_bps },
+        );
+
+        Ok(())
+    }
+
     /// Returns the current administrator address.
     pub fn get_admin(env: Env) -> Result<Address, EventRegistryError> {
         storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)
@@ -296,6 +755,60 @@ impl EventRegistry {
         storage::get_ticket_payment_contract(&env).ok_or(EventRegistryError::NotInitialized)
     }
 
+    /// Upgrades the contract to a new WASM hash. Only callable by the administrator.
+    /// Performs post-upgrade state verification to ensure critical storage is intact.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), EventRegistryError> {
+        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        admin.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        // Post-upgrade state verification
+        let verified_admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
+        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)?;
+
+        env.events().publish(
+            (AgoraEvent::ContractUpgraded,),
+            RegistryUpgradedEvent {
+                admin_address: verified_admin,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+}
+
+/// Entrypoints called by `ticket_payment`, declared in the shared
+/// `agora_types` crate so both contracts agree on their signatures.
+#[contractimpl]
+#[allow(deprecated)]
+impl EventRegistryInterface for EventRegistry {
+    /// Get event payment information including tiered pricing
+    fn get_event_payment_info(
+        env: Env,
+        event_id: String,
+    ) -> Result<PaymentInfo, EventRegistryError> {
+        match storage::get_event(&env, event_id) {
+            Some(event_info) => {
+                if event_info.status != EventStatus::OnSale {
+                    return Err(EventRegistryError::EventInactive);
+                }
+                Ok(PaymentInfo {
+                    payment_address: event_info.payment_address,
+                    platform_fee_percent: event_info.platform_fee_percent,
+                    tiers: event_info.tiers,
+                })
+            }
+            None => Err(EventRegistryError::EventNotFound),
+        }
+    }
+
+    /// Retrieves an event by its ID.
+    fn get_event(env: Env, event_id: String) -> Option<EventInfo> {
+        storage::get_event(&env, event_id)
+    }
+
     /// Increments the current_supply counter for a given event and tier.
     /// This function is restricted to calls from the authorized TicketPayment contract.
     ///
@@ -306,12 +819,15 @@ impl EventRegistry {
     /// # Errors
     /// * `UnauthorizedCaller` - If the invoker is not the registered TicketPayment contract.
     /// * `EventNotFound` - If no event with the given ID exists.
-    /// * `EventInactive` - If the event is not currently active.
+    /// * `EventInactive` - If the event is not currently on sale.
     /// * `TierNotFound` - If the tier does not exist.
+    /// * `TierRetired` - If the tier has been retired.
+    /// * `SalesNotStarted` - If the event or tier sales window has not opened yet.
+    /// * `SalesEnded` - If the event or tier sales window has closed.
     /// * `TierSupplyExceeded` - If the tier's limit has been reached.
     /// * `MaxSupplyExceeded` - If the event's max supply has been reached (when max_supply > 0).
     /// * `SupplyOverflow` - If incrementing would cause an i128 overflow.
-    pub fn increment_inventory(
+    fn increment_inventory(
         env: Env,
         event_id: String,
         tier_id: String,
@@ -328,9 +844,14 @@ impl EventRegistry {
         let mut event_info =
             storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
 
-        if !event_info.is_active {
+        if event_info.status != EventStatus::OnSale {
+            return Err(EventRegistryError::EventInactive);
+        }
+        // An event that missed its sales target will not go ahead
+        if event_info.sales_target_missed(env.ledger().timestamp()) {
             return Err(EventRegistryError::EventInactive);
         }
+        record_sales_target(&env, &event_id, &mut event_info);
 
         let quantity_i128 = quantity as i128;
 
@@ -351,6 +872,18 @@ impl EventRegistry {
             .get(tier_id.clone())
             .ok_or(EventRegistryError::TierNotFound)?;
 
+        if tier.is_retired {
+            return Err(EventRegistryError::TierRetired);
+        }
+
+        let now = env.ledger().timestamp();
+        check_sales_window(
+            now,
+            event_info.sales_start_time,
+            event_sales_end(event_info.sales_end_time, event_info.end_time),
+        )?;
+        check_sales_window(now, tier.sales_start_time, tier.sales_end_time)?;
+
         let new_tier_sold = tier
             .current_sold
             .checked_add(quantity_i128)
@@ -395,7 +928,7 @@ impl EventRegistry {
     /// * `EventNotFound` - If no event with the given ID exists.
     /// * `TierNotFound` - If the tier does not exist.
     /// * `SupplyUnderflow` - If decrementing would cause the supply to go below 0.
-    pub fn decrement_inventory(
+    fn decrement_inventory(
         env: Env,
         event_id: String,
         tier_id: String,
@@ -406,6 +939,8 @@ impl EventRegistry {
 
         let mut event_info =
             storage::get_event(&env, event_id.clone()).ok_or(EventRegistryError::EventNotFound)?;
+        // Refunds after the deadline cannot undo a target that was met by then
+        record_sales_target(&env, &event_id, &mut event_info);
 
         // Get and update tier
         let mut tier = event_info
@@ -448,37 +983,236 @@ impl EventRegistry {
         Ok(())
     }
 
-    /// Upgrades the contract to a new WASM hash. Only callable by the administrator.
-    /// Performs post-upgrade state verification to ensure critical storage is intact.
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), EventRegistryError> {
-        let admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        admin.require_auth();
+    /// Returns true if the organizer granted `role` on the event to `account`.
+    fn has_role(env: Env, event_id: String, role: EventRole, account: Address) -> bool {
+        storage::has_role(&env, event_id, role, account)
+    }
+}
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+fn validate_address(env: &Env, address: &Address) -> Result<(), EventRegistryError> {
+    if address == &env.current_contract_address() {
+        return Err(EventRegistryError::InvalidAddress);
+    }
+    Ok(())
+}
 
-        // Post-upgrade state verification
-        let verified_admin = storage::get_admin(&env).ok_or(EventRegistryError::NotInitialized)?;
-        storage::get_platform_wallet(&env).ok_or(EventRegistryError::NotInitialized)?;
+/// Checks that the event schedule is ordered, that sales open before the event
+/// ends, and that every tier sales window falls within the event's sales
+/// window. A zero timestamp means "not set" and is not compared.
+fn validate_schedule(args: &EventRegistrationArgs) -> Result<(), EventRegistryError> {
+    if args.start_time > 0 && args.end_time > 0 && args.end_time < args.start_time {
+        return Err(EventRegistryError::InvalidSchedule);
+    }
+    if args.sales_start_time > 0
+        && args.sales_end_time > 0
+        && args.sales_end_time <= args.sales_start_time
+    {
+        return Err(EventRegistryError::InvalidSchedule);
+    }
+    if args.sales_end_time > 0 && args.end_time > 0 && args.sales_end_time > args.end_time {
+        return Err(EventRegistryError::InvalidSchedule);
+    }
+    if args.sales_start_time > 0 && args.end_time > 0 && args.sales_start_time >= args.end_time {
+        return Err(EventRegistryError::InvalidSchedule);
+    }
+    let sales_end = event_sales_end(args.sales_end_time, args.end_time);
+    for tier in args.tiers.values() {
+        validate_tier_window(&tier, args.sales_start_time, sales_end)?;
+        validate_price_phases(&tier)?;
+        validate_refund_policy(&tier)?;
+    }
+    Ok(())
+}
 
-        env.events().publish(
-            (AgoraEvent::ContractUpgraded,),
-            RegistryUpgradedEvent {
-                admin_address: verified_admin,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+/// Checks that a sales target, if any, has a deadline before the event ends
+/// and can be reached.
+fn validate_sales_target(args: &EventRegistrationArgs) -> Result<(), EventRegistryError> {
+    if args.min_sales_target < 0 {
+        return Err(EventRegistryError::InvalidSalesTarget);
+    }
+    if args.min_sales_target > 0
+        && (args.sales_target_deadline == 0
+            || (args.end_time > 0 && args.sales_target_deadline >= args.end_time)
+            || (args.max_supply > 0 && args.min_sales_target > args.max_supply))
+    {
+        return Err(EventRegistryError::InvalidSalesTarget);
+    }
+    Ok(())
+}
 
-        Ok(())
+/// Marks an event's sales target met once its deadline has passed with
+/// enough tickets sold. Sales before the deadline never settle the target, as
+/// they may still expire or be refunded. The caller stores `event_info`.
+#[allow(deprecated)]
+fn record_sales_target(env: &Env, event_id: &String, event_info: &mut EventInfo) {
+    let now = env.ledger().timestamp();
+    if event_info.min_sales_target == 0
+        || event_info.sales_target_met
+        || event_info.sales_target_pending(now)
+    {
+        return;
     }
+    event_info.sales_target_met = true;
+
+    env.events().publish(
+        (AgoraEvent::SalesTargetMet,),
+        SalesTargetMetEvent {
+            event_id: event_id.clone(),
+            min_sales_target: event_info.min_sales_target,
+            timestamp: now,
+        },
+    );
 }
 
-fn validate_address(env: &Env, address: &Address) -> Result<(), EventRegistryError> {
-    if address == &env.current_contract_address() {
-        return Err(EventRegistryError::InvalidAddress);
+/// Checks that price phases are priced and strictly ordered by their end time.
+fn validate_price_phases(tier: &TicketTier) -> Result<(), EventRegistryError> {
+    let mut previous_end = 0u64;
+    for phase in tier.price_phases.iter() {
+        if phase.price < 0 || phase.ends_at <= previous_end {
+            return Err(EventRegistryError::InvalidPriceSchedule);
+        }
+        previous_end = phase.ends_at;
+    }
+    Ok(())
+}
+
+/// Checks that refund windows are ordered from the earliest cutoff to the latest,
+/// refund at most 100% and that the processing fee is not negative.
+fn validate_refund_policy(tier: &TicketTier) -> Result<(), EventRegistryError> {
+    let policy = &tier.refund_policy;
+    if policy.processing_fee < 0 {
+        return Err(EventRegistryError::InvalidRefundPolicy);
+    }
+    let mut previous_min: Option<u64> = None;
+    for window in policy.windows.iter() {
+        if window.refund_bps > 10000 {
+            return Err(EventRegistryError::InvalidRefundPolicy);
+        }
+        if let Some(previous) = previous_min {
+            if window.min_seconds_before_start >= previous {
+                return Err(EventRegistryError::InvalidRefundPolicy);
+            }
+        }
+        previous_min = Some(window.min_seconds_before_start);
+    }
+    Ok(())
+}
+
+/// A split table needs at least one payee, no payee twice, no empty shares, and
+/// shares adding up to exactly 100%.
+fn validate_revenue_splits(splits: &Vec<RevenueSplit>) -> Result<(), EventRegistryError> {
+    if splits.is_empty() {
+        return Err(EventRegistryError::InvalidRevenueSplit);
+    }
+    let mut total: u32 = 0;
+    for (index, split) in splits.iter().enumerate() {
+        if split.bps == 0 {
+            return Err(EventRegistryError::InvalidRevenueSplit);
+        }
+        if splits
+            .iter()
+            .skip(index + 1)
+            .any(|other| other.payee == split.payee)
+        {
+            return Err(EventRegistryError::InvalidRevenueSplit);
+        }
+        total = total
+            .checked_add(split.bps)
+            .ok_or(EventRegistryError::InvalidRevenueSplit)?;
+    }
+    if total != 10000 {
+        return Err(EventRegistryError::InvalidRevenueSplit);
+    }
+    Ok(())
+}
+
+/// Returns when ticket sales close for an event: its own sales end, or the end
+/// of the event when no sales end is set (0 = open-ended).
+fn event_sales_end(sales_end_time: u64, end_time: u64) -> u64 {
+    if sales_end_time > 0 {
+        sales_end_time
+    } else {
+        end_time
+    }
+}
+
+/// Checks that a tier's sales window is ordered and falls within the event's
+/// sales window. A bound of 0 is open on that side.
+fn validate_tier_window(
+    tier: &TicketTier,
+    sales_start: u64,
+    sales_end: u64,
+) -> Result<(), EventRegistryError> {
+    if tier.sales_start_time > 0
+        && tier.sales_end_time > 0
+        && tier.sales_end_time <= tier.sales_start_time
+    {
+        return Err(EventRegistryError::InvalidSchedule);
+    }
+    if tier.sales_start_time > 0 && sales_start > 0 && tier.sales_start_time < sales_start {
+        return Err(EventRegistryError::InvalidSchedule);
+    }
+    if sales_end > 0
+        && ((tier.sales_start_time > 0 && tier.sales_start_time >= sales_end)
+            || (tier.sales_end_time > 0 && tier.sales_end_time > sales_end))
+    {
+        return Err(EventRegistryError::InvalidSchedule);
     }
     Ok(())
 }
 
+/// Checks that the sum of tier limits fits within `max_supply` (0 = unlimited).
+fn validate_tier_limits(
+    tiers: &Map<String, TicketTier>,
+    max_supply: i128,
+) -> Result<(), EventRegistryError> {
+    if max_supply <= 0 {
+        return Ok(());
+    }
+    let mut total_tier_limit: i128 = 0;
+    for tier in tiers.values() {
+        total_tier_limit = total_tier_limit
+            .checked_add(tier.tier_limit)
+            .ok_or(EventRegistryError::SupplyOverflow)?;
+    }
+    if total_tier_limit > max_supply {
+        return Err(EventRegistryError::TierLimitExceedsMaxSupply);
+    }
+    Ok(())
+}
+
+/// Loads an event whose tiers can still be changed, i.e. one that is neither
+/// cancelled nor completed.
+fn get_manageable_event(env: &Env, event_id: String) -> Result<EventInfo, EventRegistryError> {
+    let event_info = storage::get_event(env, event_id).ok_or(EventRegistryError::EventNotFound)?;
+    if matches!(
+        event_info.status,
+        EventStatus::Cancelled | EventStatus::Completed
+    ) {
+        return Err(EventRegistryError::EventInactive);
+    }
+    Ok(event_info)
+}
+
+/// Rejects a purchase at `now` outside the `[start, end)` window. Zero bounds are open.
+fn check_sales_window(now: u64, start: u64, end: u64) -> Result<(), EventRegistryError> {
+    if start > 0 && now < start {
+        return Err(EventRegistryError::SalesNotStarted);
+    }
+    if end > 0 && now >= end {
+        return Err(EventRegistryError::SalesEnded);
+    }
+    Ok(())
+}
+
+/// Returns the platform fee for an organizer: their fee plan's, or the global
+/// platform fee without one.
+fn organizer_fee(env: &Env, organizer: &Address) -> u32 {
+    storage::get_organizer_fee_plan(env, organizer)
+        .and_then(|plan_id| storage::get_fee_plan(env, plan_id))
+        .unwrap_or_else(|| storage::get_platform_fee(env))
+}
+
 fn validate_metadata_cid(env: &Env, cid: &String) -> Result<(), EventRegistryError> {
     if cid.len() < 46 {
         return Err(EventRegistryError::InvalidMetadataCid);
@@ -496,5 +1230,7 @@ fn validate_metadata_cid(env: &Env, cid: &String) -> Result<(), EventRegistryErr
     Ok(())
 }
 
+#[cfg(test)]
+mod prop_test;
 #[cfg(test)]
 mod test;
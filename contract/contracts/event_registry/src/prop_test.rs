@@ -0,0 +1,249 @@
+//! Stateful property tests.
+//!
+//! Each run drives the registry through a random sequence of operations built
+//! from a seed, keeps a model of the inventory it expects, and checks the
+//! registry's invariants after every step. A failing run reports its seed and
+//! step so it can be replayed.
+
+use super::*;
+use crate::types::{EventRegistrationArgs, EventStatus, RefundPolicy, TicketTier};
+use agora_types::testutils::{id, Rng};
+use soroban_sdk::{testutils::Address as _, Address, Env, Map, String, Vec};
+
+const STATUSES: [EventStatus; 6] = [
+    EventStatus::Draft,
+    EventStatus::OnSale,
+    EventStatus::Paused,
+    EventStatus::SalesClosed,
+    EventStatus::Cancelled,
+    EventStatus::Completed,
+];
+
+/// A registry call; sales and releases are drawn most often so tiers fill up.
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Register,
+    AddTier,
+    UpdateTier,
+    RetireTier,
+    Sell,
+    Release,
+    SetStatus,
+}
+
+impl Op {
+    fn generate(rng: &mut Rng) -> Op {
+        match rng.below(10) {
+            0 => Op::Register,
+            1 => Op::AddTier,
+            2 => Op::UpdateTier,
+            3 => Op::RetireTier,
+            4..=6 => Op::Sell,
+            7 | 8 => Op::Release,
+            _ => Op::SetStatus,
+        }
+    }
+}
+
+fn random_tier(env: &Env, rng: &mut Rng) -> TicketTier {
+    TicketTier {
+        name: String::from_str(env, "Tier"),
+        price: rng.below(1000) as i128,
+        price_phases: Vec::new(env),
+        tier_limit: rng.below(12) as i128,
+        // Whatever the caller claims, new tiers must start empty
+        current_sold: rng.below(5) as i128,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        is_refundable: true,
+        refund_policy: RefundPolicy::full_refund(env),
+        is_retired: rng.below(4) == 0,
+    }
+}
+
+struct Harness<'a> {
+    env: &'a Env,
+    client: EventRegistryClient<'a>,
+    organizer: Address,
+    rng: Rng,
+    /// (seed, step, op) being run, reported by failed assertions
+    at: (u64, u32, Op),
+    events: Vec<String>,
+    /// Tickets the model expects sold, keyed by (event_id, tier_id)
+    sold: Map<(String, String), i128>,
+}
+
+impl<'a> Harness<'a> {
+    fn new(env: &'a Env, seed: u64) -> Self {
+        env.mock_all_auths();
+        let contract_id = env.register(EventRegistry, ());
+        let client = EventRegistryClient::new(env, &contract_id);
+        client.initialize(&Address::generate(env), &Address::generate(env), &500);
+        client.set_ticket_payment_contract(&Address::generate(env));
+
+        Harness {
+            env,
+            client,
+            organizer: Address::generate(env),
+            rng: Rng(seed),
+            at: (seed, 0, Op::Register),
+            events: Vec::new(env),
+            sold: Map::new(env),
+        }
+    }
+
+    fn pick_tier(&mut self, event_id: &String) -> Option<String> {
+        let tiers = self.client.get_event(event_id)?.tiers.keys();
+        self.rng.pick(&tiers)
+    }
+
+    fn apply(&mut self, op: Op) {
+        let env = self.env;
+        let Some(event_id) = self.rng.pick(&self.events) else {
+            if matches!(op, Op::Register) {
+                self.register();
+            }
+            return;
+        };
+
+        match op {
+            Op::Register => self.register(),
+            Op::AddTier => {
+                let tier_id = id(env, b't', self.rng.below(6));
+                let tier = random_tier(env, &mut self.rng);
+                if self.client.try_add_tier(&event_id, &tier_id, &tier).is_ok() {
+                    self.sold.set((event_id, tier_id), 0);
+                }
+            }
+            Op::UpdateTier => {
+                if let Some(tier_id) = self.pick_tier(&event_id) {
+                    let price = self.rng.below(1000) as i128;
+                    let limit = self.rng.below(12) as i128;
+                    let _ = self
+                        .client
+                        .try_update_tier(&event_id, &tier_id, &price, &limit);
+                }
+            }
+            Op::RetireTier => {
+                if let Some(tier_id) = self.pick_tier(&event_id) {
+                    let _ = self.client.try_retire_tier(&event_id, &tier_id);
+                }
+            }
+            Op::Sell => {
+                if let Some(tier_id) = self.pick_tier(&event_id) {
+                    let quantity = self.rng.below(4) + 1;
+                    let result = self
+                        .client
+                        .try_increment_inventory(&event_id, &tier_id, &quantity);
+                    if result.is_ok() {
+                        let key = (event_id, tier_id);
+                        let sold = self.sold.get(key.clone()).unwrap_or(0);
+                        self.sold.set(key, sold + quantity as i128);
+                    }
+                }
+            }
+            Op::Release => {
+                if let Some(tier_id) = self.pick_tier(&event_id) {
+                    let result = self.client.try_decrement_inventory(&event_id, &tier_id);
+                    if result.is_ok() {
+                        let key = (event_id, tier_id);
+                        let sold = self.sold.get(key.clone()).unwrap_or(0);
+                        self.sold.set(key, sold - 1);
+                    }
+                }
+            }
+            Op::SetStatus => {
+                let before = self.client.get_event(&event_id).unwrap().status;
+                let next = STATUSES[self.rng.below(STATUSES.len() as u32) as usize];
+                let result = self.client.try_update_event_status(&event_id, &next);
+                let after = self.client.get_event(&event_id).unwrap().status;
+                if result.is_ok() {
+                    assert!(before.can_transition_to(next), "{:?}", self.at);
+                    assert_eq!(after, next, "{:?}", self.at);
+                } else {
+                    assert_eq!(after, before, "{:?}", self.at);
+                }
+            }
+        }
+    }
+
+    fn register(&mut self) {
+        let env = self.env;
+        let event_id = id(env, b'e', self.events.len());
+        let mut tiers = Map::new(env);
+        for index in 0..self.rng.below(4) {
+            tiers.set(id(env, b't', index), random_tier(env, &mut self.rng));
+        }
+        let max_supply = [0, 10, 30][self.rng.below(3) as usize];
+
+        let result = self.client.try_register_event(&EventRegistrationArgs {
+            event_id: event_id.clone(),
+            organizer_address: self.organizer.clone(),
+            payment_address: self.organizer.clone(),
+            metadata_cid: String::from_str(
+                env,
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
+            max_supply,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            milestone_plan: None,
+            revenue_splits: None,
+            min_sales_target: 0,
+            sales_target_deadline: 0,
+            draft: self.rng.below(4) == 0,
+            tiers: tiers.clone(),
+        });
+        if result.is_ok() {
+            for tier_id in tiers.keys().iter() {
+                self.sold.set((event_id.clone(), tier_id), 0);
+            }
+            self.events.push_back(event_id);
+        }
+    }
+
+    fn check_invariants(&self) {
+        let at = self.at;
+        for event_id in self.events.iter() {
+            let event = self.client.get_event(&event_id).unwrap();
+            let mut total_sold = 0;
+            let mut total_limit = 0;
+            for (tier_id, tier) in event.tiers.iter() {
+                assert!(tier.current_sold >= 0, "{at:?}");
+                assert!(tier.current_sold <= tier.tier_limit, "{at:?}");
+                assert_eq!(
+                    Some(tier.current_sold),
+                    self.sold.get((event_id.clone(), tier_id)),
+                    "{at:?}"
+                );
+                total_sold += tier.current_sold;
+                total_limit += tier.tier_limit;
+            }
+            assert_eq!(event.current_supply, total_sold, "{at:?}");
+            if event.max_supply > 0 {
+                assert!(event.current_supply <= event.max_supply, "{at:?}");
+                assert!(total_limit <= event.max_supply, "{at:?}");
+            }
+        }
+    }
+}
+
+fn run(seed: u64, steps: u32) {
+    let env = Env::default();
+    let mut harness = Harness::new(&env, seed);
+    for step in 0..steps {
+        let op = Op::generate(&mut harness.rng);
+        harness.at = (seed, step, op);
+        harness.apply(op);
+        harness.check_invariants();
+    }
+}
+
+#[test]
+fn prop_inventory_and_status_invariants() {
+    for seed in 1..=8 {
+        run(seed, 120);
+    }
+}
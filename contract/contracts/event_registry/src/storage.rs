@@ -1,4 +1,4 @@
-use crate::types::{DataKey, EventInfo};
+use crate::types::{DataKey, EventInfo, EventRole};
 use soroban_sdk::{Address, Env, String, Vec};
 
 /// Sets the administrator address of the contract.
@@ -118,3 +118,57 @@ pub fn get_ticket_payment_contract(env: &Env) -> Option<Address> {
         .persistent()
         .get(&DataKey::TicketPaymentContract)
 }
+
+/// Grants a delegated role on an event to an account.
+pub fn grant_role(env: &Env, event_id: String, role: EventRole, account: Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::EventRole(event_id, role, account), &true);
+}
+
+/// Removes a delegated role on an event from an account.
+pub fn revoke_role(env: &Env, event_id: String, role: EventRole, account: Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::EventRole(event_id, role, account));
+}
+
+/// Checks whether an account holds a delegated role on an event.
+pub fn has_role(env: &Env, event_id: String, role: EventRole, account: Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::EventRole(event_id, role, account))
+}
+
+/// Creates or updates a fee plan.
+pub fn set_fee_plan(env: &Env, plan_id: String, fee_bps: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FeePlan(plan_id), &fee_bps);
+}
+
+/// Retrieves the platform fee of a fee plan, if it exists.
+pub fn get_fee_plan(env: &Env, plan_id: String) -> Option<u32> {
+    env.storage().persistent().get(&DataKey::FeePlan(plan_id))
+}
+
+/// Assigns a fee plan to an organizer.
+pub fn set_organizer_fee_plan(env: &Env, organizer: &Address, plan_id: String) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::OrganizerFeePlan(organizer.clone()), &plan_id);
+}
+
+/// Removes an organizer's fee plan, returning it to the global platform fee.
+pub fn remove_organizer_fee_plan(env: &Env, organizer: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OrganizerFeePlan(organizer.clone()));
+}
+
+/// Retrieves the fee plan ID assigned to an organizer, if any.
+pub fn get_organizer_fee_plan(env: &Env, organizer: &Address) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OrganizerFeePlan(organizer.clone()))
+}
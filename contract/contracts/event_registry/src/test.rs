@@ -1,7 +1,13 @@
 use super::*;
 use crate::error::EventRegistryError;
-use crate::types::{EventInfo, EventRegistrationArgs, TicketTier};
-use soroban_sdk::{testutils::Address as _, Address, Env, Map, String};
+use crate::types::{
+    EventInfo, EventRegistrationArgs, EventRole, EventStatus, PricePhase, RefundPolicy,
+    RefundWindow, RevenueSplit, TicketTier,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, Env, Map, String, TryIntoVal, Vec,
+};
 
 #[test]
 fn test_initialize() {
@@ -101,6 +107,102 @@ fn test_set_platform_fee_unauthorized() {
     client.set_platform_fee(&10);
 }
 
+#[test]
+fn test_fee_plans_and_event_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let pro = String::from_str(&env, "pro");
+    let pro_organizer = Address::generate(&env);
+    let basic_organizer = Address::generate(&env);
+
+    let result = client.try_assign_fee_plan(&pro_organizer, &Some(pro.clone()));
+    assert_eq!(result, Err(Ok(EventRegistryError::FeePlanNotFound)));
+    let result = client.try_set_fee_plan(&pro, &10001);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidFeePercent)));
+
+    client.set_fee_plan(&pro, &0);
+    client.assign_fee_plan(&pro_organizer, &Some(pro.clone()));
+    assert_eq!(client.get_fee_plan(&pro), Some(0));
+    assert_eq!(
+        client.get_organizer_fee_plan(&pro_organizer),
+        Some(pro.clone())
+    );
+    assert_eq!(client.get_organizer_fee(&pro_organizer), 0);
+    assert_eq!(client.get_organizer_fee(&basic_organizer), 500);
+
+    let args = |event_id: &str, organizer: &Address| EventRegistrationArgs {
+        event_id: String::from_str(&env, event_id),
+        organizer_address: organizer.clone(),
+        payment_address: organizer.clone(),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers: Map::new(&env),
+    };
+    client.register_event(&args("pro_event", &pro_organizer));
+    client.register_event(&args("basic_event", &basic_organizer));
+    let pro_event = String::from_str(&env, "pro_event");
+    let basic_event = String::from_str(&env, "basic_event");
+    assert_eq!(
+        client
+            .get_event_payment_info(&pro_event)
+            .platform_fee_percent,
+        0
+    );
+    assert_eq!(
+        client
+            .get_event_payment_info(&basic_event)
+            .platform_fee_percent,
+        500
+    );
+
+    // Plan and global fee changes only apply to events registered afterwards
+    client.set_fee_plan(&pro, &100);
+    client.set_platform_fee(&300);
+    assert_eq!(
+        client
+            .get_event_payment_info(&pro_event)
+            .platform_fee_percent,
+        0
+    );
+    assert_eq!(
+        client
+            .get_event_payment_info(&basic_event)
+            .platform_fee_percent,
+        500
+    );
+
+    client.set_event_fee(&basic_event, &250);
+    assert_eq!(
+        client
+            .get_event_payment_info(&basic_event)
+            .platform_fee_percent,
+        250
+    );
+    let result = client.try_set_event_fee(&String::from_str(&env, "missing"), &250);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventNotFound)));
+
+    client.assign_fee_plan(&pro_organizer, &None);
+    assert_eq!(client.get_organizer_fee_plan(&pro_organizer), None);
+    assert_eq!(client.get_organizer_fee(&pro_organizer), 300);
+}
+
 #[test]
 fn test_storage_operations() {
     let env = Env::default();
@@ -121,7 +223,7 @@ fn test_storage_operations() {
         organizer_address: organizer.clone(),
         payment_address: payment_address.clone(),
         platform_fee_percent: 5,
-        is_active: true,
+        status: EventStatus::OnSale,
         created_at: env.ledger().timestamp(),
         metadata_cid: String::from_str(
             &env,
@@ -129,7 +231,16 @@ fn test_storage_operations() {
         ),
         max_supply: 100,
         current_supply: 0,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        sales_target_met: false,
+        completed_at: 0,
         tiers,
     };
 
@@ -142,7 +253,7 @@ fn test_storage_operations() {
     assert_eq!(stored_event.organizer_address, organizer);
     assert_eq!(stored_event.payment_address, payment_address);
     assert_eq!(stored_event.platform_fee_percent, 5);
-    assert!(stored_event.is_active);
+    assert_eq!(stored_event.status, EventStatus::OnSale);
     assert_eq!(stored_event.max_supply, 100);
     assert_eq!(stored_event.current_supply, 0);
 
@@ -164,7 +275,7 @@ fn test_organizer_events_list() {
         organizer_address: organizer.clone(),
         payment_address: payment_address.clone(),
         platform_fee_percent: 5,
-        is_active: true,
+        status: EventStatus::OnSale,
         created_at: 100,
         metadata_cid: String::from_str(
             &env,
@@ -172,7 +283,16 @@ fn test_organizer_events_list() {
         ),
         max_supply: 50,
         current_supply: 0,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        sales_target_met: false,
+        completed_at: 0,
         tiers: tiers.clone(),
     };
 
@@ -181,7 +301,7 @@ fn test_organizer_events_list() {
         organizer_address: organizer.clone(),
         payment_address: payment_address.clone(),
         platform_fee_percent: 5,
-        is_active: true,
+        status: EventStatus::OnSale,
         created_at: 200,
         metadata_cid: String::from_str(
             &env,
@@ -189,7 +309,16 @@ fn test_organizer_events_list() {
         ),
         max_supply: 0,
         current_supply: 0,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        sales_target_met: false,
+        completed_at: 0,
         tiers,
     };
 
@@ -231,9 +360,14 @@ fn test_register_event_success() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 100,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -243,7 +377,15 @@ fn test_register_event_success() {
         payment_address: payment_addr.clone(),
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -283,7 +425,15 @@ fn test_register_event_unlimited_supply() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 0,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -318,7 +468,15 @@ fn test_register_duplicate_event_fails() {
         payment_address: payment_addr.clone(),
         metadata_cid: metadata_cid.clone(),
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers: tiers.clone(),
     });
 
@@ -328,7 +486,15 @@ fn test_register_duplicate_event_fails() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
     assert_eq!(result, Err(Ok(EventRegistryError::EventAlreadyExists)));
@@ -360,7 +526,15 @@ fn test_get_event_payment_info() {
         payment_address: payment_addr.clone(),
         metadata_cid,
         max_supply: 50,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -395,13 +569,21 @@ fn test_update_event_status() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
-    client.update_event_status(&event_id, &false);
+    client.update_event_status(&event_id, &EventStatus::Paused);
 
     let event_info = client.get_event(&event_id).unwrap();
-    assert!(!event_info.is_active);
+    assert_eq!(event_info.status, EventStatus::Paused);
 }
 
 #[test]
@@ -429,10 +611,18 @@ fn test_event_inactive_error() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
-    client.update_event_status(&event_id, &false);
+    client.update_event_status(&event_id, &EventStatus::Paused);
 
     let result = client.try_get_event_payment_info(&event_id);
     assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
@@ -464,7 +654,15 @@ fn test_complete_event_lifecycle() {
         payment_address: payment_addr.clone(),
         metadata_cid,
         max_supply: 200,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -476,13 +674,13 @@ fn test_complete_event_lifecycle() {
     assert_eq!(org_events.len(), 1);
     assert!(org_events.contains(&event_id));
 
-    client.update_event_status(&event_id, &false);
+    client.update_event_status(&event_id, &EventStatus::Cancelled);
 
     let result = client.try_get_event_payment_info(&event_id);
     assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
 
     let event_info = client.get_event(&event_id).unwrap();
-    assert!(!event_info.is_active);
+    assert_eq!(event_info.status, EventStatus::Cancelled);
 }
 
 #[test]
@@ -507,11 +705,19 @@ fn test_update_metadata_success() {
     let tiers = Map::new(&env);
     client.register_event(&EventRegistrationArgs {
         event_id: event_id.clone(),
-        organizer_address: organizer,
+        organizer_address: organizer.clone(),
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -519,7 +725,7 @@ fn test_update_metadata_success() {
         &env,
         "bafkreifh22222222222222222222222222222222222222222222222222",
     );
-    client.update_metadata(&event_id, &new_metadata_cid);
+    client.update_metadata(&event_id, &new_metadata_cid, &organizer);
 
     let event_info = client.get_event(&event_id).unwrap();
     assert_eq!(event_info.metadata_cid, new_metadata_cid);
@@ -547,11 +753,19 @@ fn test_update_metadata_invalid_cid() {
     let tiers = Map::new(&env);
     client.register_event(&EventRegistrationArgs {
         event_id: event_id.clone(),
-        organizer_address: organizer,
+        organizer_address: organizer.clone(),
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -559,17 +773,85 @@ fn test_update_metadata_invalid_cid() {
         &env,
         "Qafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
     );
-    let result_wrong_char = client.try_update_metadata(&event_id, &wrong_char_cid);
+    let result_wrong_char = client.try_update_metadata(&event_id, &wrong_char_cid, &organizer);
     assert_eq!(
         result_wrong_char,
         Err(Ok(EventRegistryError::InvalidMetadataCid))
     );
 
     let short_cid = String::from_str(&env, "bafy");
-    let result = client.try_update_metadata(&event_id, &short_cid);
+    let result = client.try_update_metadata(&event_id, &short_cid, &organizer);
     assert_eq!(result, Err(Ok(EventRegistryError::InvalidMetadataCid)));
 }
 
+#[test]
+fn test_event_roles() {
+    let env = Env::default();
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let manager = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin, &platform_wallet, &500);
+
+    let event_id = String::from_str(&env, "event_roles");
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        organizer_address: organizer.clone(),
+        payment_address: payment_addr,
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers: Map::new(&env),
+    });
+
+    let new_metadata_cid = String::from_str(
+        &env,
+        "bafkreifh22222222222222222222222222222222222222222222222222",
+    );
+
+    // Without the role the manager cannot edit metadata
+    assert!(!client.has_role(&event_id, &EventRole::Manager, &manager));
+    let res = client.try_update_metadata(&event_id, &new_metadata_cid, &manager);
+    assert_eq!(res, Err(Ok(EventRegistryError::Unauthorized)));
+
+    client.grant_role(&event_id, &EventRole::Manager, &manager);
+    assert!(client.has_role(&event_id, &EventRole::Manager, &manager));
+    // Roles are independent of each other
+    assert!(!client.has_role(&event_id, &EventRole::Scanner, &manager));
+
+    client.update_metadata(&event_id, &new_metadata_cid, &manager);
+    assert_eq!(
+        client.get_event(&event_id).unwrap().metadata_cid,
+        new_metadata_cid
+    );
+
+    client.revoke_role(&event_id, &EventRole::Manager, &manager);
+    assert!(!client.has_role(&event_id, &EventRole::Manager, &manager));
+    let res = client.try_update_metadata(&event_id, &new_metadata_cid, &manager);
+    assert_eq!(res, Err(Ok(EventRegistryError::Unauthorized)));
+
+    let missing = String::from_str(&env, "missing");
+    let res = client.try_grant_role(&missing, &EventRole::Scanner, &manager);
+    assert_eq!(res, Err(Ok(EventRegistryError::EventNotFound)));
+}
+
 // ==================== Inventory / Supply Tests ====================
 
 #[test]
@@ -620,9 +902,14 @@ fn test_increment_inventory_success() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 10,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -632,7 +919,15 @@ fn test_increment_inventory_success() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 10,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -682,9 +977,14 @@ fn test_increment_inventory_max_supply_exceeded() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 2,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -694,7 +994,15 @@ fn test_increment_inventory_max_supply_exceeded() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 2,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -739,9 +1047,14 @@ fn test_increment_inventory_unlimited_supply() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 1000,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -751,7 +1064,15 @@ fn test_increment_inventory_unlimited_supply() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 0,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -814,9 +1135,77 @@ fn test_increment_inventory_inactive_event() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 100,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
+        },
+    );
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid,
+        max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers,
+    });
+
+    client.update_event_status(&event_id, &EventStatus::Paused);
+
+    let result = client.try_increment_inventory(&event_id, &tier_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+}
+
+#[test]
+fn test_event_status_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let ticket_payment = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500);
+    client.set_ticket_payment_contract(&ticket_payment);
+
+    let event_id = String::from_str(&env, "status_event");
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    let mut tiers = Map::new(&env);
+    let tier_id = String::from_str(&env, "general");
+    tiers.set(
+        tier_id.clone(),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5000000,
+            price_phases: Vec::new(&env),
+            tier_limit: 100,
+            current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            is_refundable: false,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
     client.register_event(&EventRegistrationArgs {
@@ -825,14 +1214,93 @@ fn test_increment_inventory_inactive_event() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
+    assert_eq!(
+        client.get_event(&event_id).unwrap().status,
+        EventStatus::OnSale
+    );
 
-    client.update_event_status(&event_id, &false);
+    // Pausing and resuming sales keeps the event alive
+    client.update_event_status(&event_id, &EventStatus::Paused);
+    client.update_event_status(&event_id, &EventStatus::OnSale);
+    client.increment_inventory(&event_id, &tier_id, &1);
 
+    // Closing sales blocks purchases until the event completes
+    client.update_event_status(&event_id, &EventStatus::SalesClosed);
     let result = client.try_increment_inventory(&event_id, &tier_id, &1);
     assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+
+    client.update_event_status(&event_id, &EventStatus::Completed);
+    assert_eq!(
+        client.get_event(&event_id).unwrap().status,
+        EventStatus::Completed
+    );
+
+    // Completed is terminal
+    let result = client.try_update_event_status(&event_id, &EventStatus::OnSale);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+    let result = client.try_update_event_status(&event_id, &EventStatus::Cancelled);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_cancelled_event_cannot_be_reopened() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500);
+
+    let event_id = String::from_str(&env, "cancelled_event");
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid,
+        max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers: Map::new(&env),
+    });
+
+    // Draft can only be reached through registration, never by transition
+    let result = client.try_update_event_status(&event_id, &EventStatus::Draft);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+
+    client.update_event_status(&event_id, &EventStatus::Cancelled);
+
+    let result = client.try_update_event_status(&event_id, &EventStatus::OnSale);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+    let result = client.try_update_event_status(&event_id, &EventStatus::Paused);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
 }
 
 #[test]
@@ -864,9 +1332,14 @@ fn test_increment_inventory_persists_across_reads() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 50,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
     client.register_event(&EventRegistrationArgs {
@@ -875,7 +1348,15 @@ fn test_increment_inventory_persists_across_reads() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 50,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -919,9 +1400,14 @@ fn test_tier_limit_exceeds_max_supply() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 60,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
     tiers.set(
@@ -929,9 +1415,14 @@ fn test_tier_limit_exceeds_max_supply() {
         TicketTier {
             name: String::from_str(&env, "VIP"),
             price: 10000000,
+            price_phases: Vec::new(&env),
             tier_limit: 50,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -941,7 +1432,15 @@ fn test_tier_limit_exceeds_max_supply() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
     assert_eq!(
@@ -979,9 +1478,14 @@ fn test_tier_not_found() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 100,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -991,7 +1495,15 @@ fn test_tier_not_found() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -1030,9 +1542,14 @@ fn test_tier_supply_exceeded() {
         TicketTier {
             name: String::from_str(&env, "VIP"),
             price: 10000000,
+            price_phases: Vec::new(&env),
             tier_limit: 3,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -1042,7 +1559,15 @@ fn test_tier_supply_exceeded() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -1086,9 +1611,14 @@ fn test_multiple_tiers_inventory() {
         TicketTier {
             name: String::from_str(&env, "General"),
             price: 5000000,
+            price_phases: Vec::new(&env),
             tier_limit: 50,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
     tiers.set(
@@ -1096,9 +1626,14 @@ fn test_multiple_tiers_inventory() {
         TicketTier {
             name: String::from_str(&env, "VIP"),
             price: 10000000,
+            price_phases: Vec::new(&env),
             tier_limit: 20,
             current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
         },
     );
 
@@ -1108,7 +1643,15 @@ fn test_multiple_tiers_inventory() {
         payment_address: payment_addr,
         metadata_cid,
         max_supply: 70,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
         milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
         tiers,
     });
 
@@ -1125,3 +1668,960 @@ fn test_multiple_tiers_inventory() {
     let vip_tier = event_info.tiers.get(vip_id).unwrap();
     assert_eq!(vip_tier.current_sold, 1);
 }
+
+#[test]
+fn test_register_event_invalid_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500);
+
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    let base_args = EventRegistrationArgs {
+        event_id: String::from_str(&env, "scheduled_event"),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid,
+        max_supply: 100,
+        start_time: 2000,
+        end_time: 3000,
+        sales_start_time: 1000,
+        sales_end_time: 2000,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers: Map::new(&env),
+    };
+
+    // Event ends before it starts
+    let mut args = base_args.clone();
+    args.end_time = 1500;
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSchedule)));
+
+    // Sales close before they open
+    let mut args = base_args.clone();
+    args.sales_end_time = 500;
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSchedule)));
+
+    // Sales run past the end of the event
+    let mut args = base_args.clone();
+    args.sales_end_time = 3500;
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSchedule)));
+
+    // Tier window is inverted
+    let mut args = base_args.clone();
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        String::from_str(&env, "general"),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5000000,
+            price_phases: Vec::new(&env),
+            tier_limit: 10,
+            current_sold: 0,
+            sales_start_time: 1800,
+            sales_end_time: 1200,
+            is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
+        },
+    );
+    args.tiers = tiers;
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSchedule)));
+
+    // Sales open only after the event has ended
+    let mut args = base_args.clone();
+    args.sales_start_time = 3000;
+    args.sales_end_time = 0;
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSchedule)));
+
+    // Tier windows must sit inside the event sales window (1000..2000)
+    let tier_window = |start: u64, end: u64| {
+        let mut tiers = Map::new(&env);
+        tiers.set(
+            String::from_str(&env, "general"),
+            TicketTier {
+                name: String::from_str(&env, "General"),
+                price: 5000000,
+                price_phases: Vec::new(&env),
+                tier_limit: 10,
+                current_sold: 0,
+                sales_start_time: start,
+                sales_end_time: end,
+                is_refundable: true,
+                refund_policy: RefundPolicy::full_refund(&env),
+                is_retired: false,
+            },
+        );
+        tiers
+    };
+    for (start, end) in [(500, 1500), (1200, 2500), (2000, 0), (0, 2100)] {
+        let mut args = base_args.clone();
+        args.tiers = tier_window(start, end);
+        let result = client.try_register_event(&args);
+        assert_eq!(result, Err(Ok(EventRegistryError::InvalidSchedule)));
+    }
+
+    // Without a sales end, the tier window is bounded by the end of the event
+    let mut args = base_args.clone();
+    args.event_id = String::from_str(&env, "open_sales_event");
+    args.sales_end_time = 0;
+    args.tiers = tier_window(1200, 3500);
+    let result = client.try_register_event(&args);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSchedule)));
+    args.tiers = tier_window(1200, 2500);
+    client.register_event(&args);
+
+    client.register_event(&base_args);
+    let event_info = client.get_event(&base_args.event_id).unwrap();
+    assert_eq!(event_info.start_time, 2000);
+    assert_eq!(event_info.end_time, 3000);
+    assert_eq!(event_info.sales_start_time, 1000);
+    assert_eq!(event_info.sales_end_time, 2000);
+}
+
+#[test]
+fn test_increment_inventory_respects_sales_windows() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let payment_addr = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let ticket_payment = Address::generate(&env);
+
+    client.initialize(&admin, &platform_wallet, &500);
+    client.set_ticket_payment_contract(&ticket_payment);
+
+    let event_id = String::from_str(&env, "window_event");
+    let metadata_cid = String::from_str(
+        &env,
+        "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    let general_id = String::from_str(&env, "general");
+    let presale_id = String::from_str(&env, "presale");
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        general_id.clone(),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5000000,
+            price_phases: Vec::new(&env),
+            tier_limit: 50,
+            current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
+        },
+    );
+    tiers.set(
+        presale_id.clone(),
+        TicketTier {
+            name: String::from_str(&env, "Presale"),
+            price: 4000000,
+            price_phases: Vec::new(&env),
+            tier_limit: 50,
+            current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 1500,
+            is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
+        },
+    );
+
+    // Sales close at end_time since no explicit sales_end_time is set
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid,
+        max_supply: 100,
+        start_time: 2000,
+        end_time: 3000,
+        sales_start_time: 1000,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers,
+    });
+
+    env.ledger().with_mut(|li| li.timestamp = 999);
+    let result = client.try_increment_inventory(&event_id, &general_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesNotStarted)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.increment_inventory(&event_id, &general_id, &1);
+    client.increment_inventory(&event_id, &presale_id, &1);
+
+    // Presale tier closes before the event-wide window
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    let result = client.try_increment_inventory(&event_id, &presale_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesEnded)));
+    client.increment_inventory(&event_id, &general_id, &1);
+
+    // Nothing can be bought once the event is over
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    let result = client.try_increment_inventory(&event_id, &general_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesEnded)));
+
+    let event_info = client.get_event(&event_id).unwrap();
+    assert_eq!(event_info.current_supply, 3);
+}
+
+fn register_tiered_event(
+    env: &Env,
+    client: &EventRegistryClient,
+    event_id: &String,
+    max_supply: i128,
+) {
+    let organizer = Address::generate(env);
+    let payment_addr = Address::generate(env);
+    let mut tiers = Map::new(env);
+    tiers.set(
+        String::from_str(env, "general"),
+        TicketTier {
+            name: String::from_str(env, "General"),
+            price: 5000000,
+            price_phases: Vec::new(env),
+            tier_limit: 60,
+            current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(env),
+            is_retired: false,
+        },
+    );
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        organizer_address: organizer,
+        payment_address: payment_addr,
+        metadata_cid: String::from_str(
+            env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers,
+    });
+}
+
+#[test]
+fn test_add_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let event_id = String::from_str(&env, "tiered_event");
+    register_tiered_event(&env, &client, &event_id, 100);
+
+    let vip_id = String::from_str(&env, "vip");
+    let vip = TicketTier {
+        name: String::from_str(&env, "VIP"),
+        price: 20000000,
+        price_phases: Vec::new(&env),
+        tier_limit: 40,
+        current_sold: 7, // ignored, new tiers start empty
+        sales_start_time: 0,
+        sales_end_time: 0,
+        is_refundable: false,
+        refund_policy: RefundPolicy::full_refund(&env),
+        is_retired: false,
+    };
+    client.add_tier(&event_id, &vip_id, &vip);
+
+    let stored = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(vip_id.clone())
+        .unwrap();
+    assert_eq!(stored.price, 20000000);
+    assert_eq!(stored.tier_limit, 40);
+    assert_eq!(stored.current_sold, 0);
+
+    let result = client.try_add_tier(&event_id, &vip_id, &vip);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierAlreadyExists)));
+
+    // General (60) + VIP (40) already fill max_supply
+    let mut extra = vip.clone();
+    extra.tier_limit = 1;
+    let result = client.try_add_tier(&event_id, &String::from_str(&env, "extra"), &extra);
+    assert_eq!(
+        result,
+        Err(Ok(EventRegistryError::TierLimitExceedsMaxSupply))
+    );
+
+    let mut negative = vip.clone();
+    negative.price = -1;
+    let result = client.try_add_tier(&event_id, &String::from_str(&env, "neg"), &negative);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidTierConfig)));
+}
+
+#[test]
+fn test_update_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    let event_id = String::from_str(&env, "tiered_event");
+    let tier_id = String::from_str(&env, "general");
+    register_tiered_event(&env, &client, &event_id, 100);
+    client.increment_inventory(&event_id, &tier_id, &5);
+
+    let result = client.try_update_tier(&event_id, &tier_id, &6000000, &4);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierLimitBelowSold)));
+
+    let result = client.try_update_tier(&event_id, &tier_id, &6000000, &101);
+    assert_eq!(
+        result,
+        Err(Ok(EventRegistryError::TierLimitExceedsMaxSupply))
+    );
+
+    let result = client.try_update_tier(&event_id, &String::from_str(&env, "nope"), &1, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierNotFound)));
+
+    client.update_tier(&event_id, &tier_id, &6000000, &5);
+    let tier = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone())
+        .unwrap();
+    assert_eq!(tier.price, 6000000);
+    assert_eq!(tier.tier_limit, 5);
+    assert_eq!(tier.current_sold, 5);
+
+    let result = client.try_increment_inventory(&event_id, &tier_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierSupplyExceeded)));
+}
+
+#[test]
+fn test_retire_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    let event_id = String::from_str(&env, "tiered_event");
+    let tier_id = String::from_str(&env, "general");
+    register_tiered_event(&env, &client, &event_id, 60);
+    client.increment_inventory(&event_id, &tier_id, &3);
+
+    client.retire_tier(&event_id, &tier_id);
+
+    let tier = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone())
+        .unwrap();
+    assert!(tier.is_retired);
+    assert_eq!(tier.tier_limit, 3);
+    assert_eq!(tier.current_sold, 3);
+
+    let result = client.try_increment_inventory(&event_id, &tier_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierRetired)));
+    let result = client.try_update_tier(&event_id, &tier_id, &1, &10);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierRetired)));
+    let result = client.try_retire_tier(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::TierRetired)));
+
+    // Refunds still flow through a retired tier
+    client.decrement_inventory(&event_id, &tier_id);
+
+    // The unsold capacity is available for a replacement tier
+    client.add_tier(
+        &event_id,
+        &String::from_str(&env, "late"),
+        &TicketTier {
+            name: String::from_str(&env, "Late"),
+            price: 7000000,
+            price_phases: Vec::new(&env),
+            tier_limit: 57,
+            current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
+        },
+    );
+}
+
+#[test]
+fn test_tier_management_blocked_on_cancelled_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let event_id = String::from_str(&env, "tiered_event");
+    let tier_id = String::from_str(&env, "general");
+    register_tiered_event(&env, &client, &event_id, 100);
+    client.update_event_status(&event_id, &EventStatus::Cancelled);
+
+    let result = client.try_update_tier(&event_id, &tier_id, &1, &10);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+    let result = client.try_retire_tier(&event_id, &tier_id);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+}
+
+#[test]
+fn test_tier_active_price_phases() {
+    let env = Env::default();
+
+    let mut price_phases = Vec::new(&env);
+    price_phases.push_back(PricePhase {
+        name: String::from_str(&env, "Super Early"),
+        price: 50,
+        ends_at: 1000,
+    });
+    price_phases.push_back(PricePhase {
+        name: String::from_str(&env, "Early"),
+        price: 75,
+        ends_at: 2000,
+    });
+    let tier = TicketTier {
+        name: String::from_str(&env, "General"),
+        price: 100,
+        price_phases,
+        tier_limit: 10,
+        current_sold: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        is_refundable: true,
+        refund_policy: RefundPolicy::full_refund(&env),
+        is_retired: false,
+    };
+
+    assert_eq!(tier.active_price(0), (0, 50));
+    assert_eq!(tier.active_price(1000), (0, 50));
+    assert_eq!(tier.active_price(1001), (1, 75));
+    assert_eq!(tier.active_price(2000), (1, 75));
+    assert_eq!(tier.active_price(2001), (2, 100));
+}
+
+#[test]
+fn test_update_price_phases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(500);
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let event_id = String::from_str(&env, "tiered_event");
+    let tier_id = String::from_str(&env, "general");
+    register_tiered_event(&env, &client, &event_id, 100);
+    let regular_price = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone())
+        .unwrap()
+        .price;
+
+    let mut price_phases = Vec::new(&env);
+    price_phases.push_back(PricePhase {
+        name: String::from_str(&env, "Early"),
+        price: 75,
+        ends_at: 2000,
+    });
+    client.update_price_phases(&event_id, &tier_id, &price_phases);
+
+    let (_, topics, data) = env.events().all().iter().last().unwrap();
+    let topic: crate::events::AgoraEvent = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, crate::events::AgoraEvent::TierUpdated);
+    let payload: crate::events::TierUpdatedEvent = data.try_into_val(&env).unwrap();
+    assert_eq!(payload.old_price, regular_price);
+    assert_eq!(payload.new_price, 75);
+    assert_eq!(payload.old_limit, payload.new_limit);
+
+    let tier = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone())
+        .unwrap();
+    assert_eq!(tier.price_phases, price_phases);
+    assert_eq!(tier.active_price(500), (0, 75));
+
+    let mut unordered = price_phases.clone();
+    unordered.push_back(PricePhase {
+        name: String::from_str(&env, "Super Early"),
+        price: 50,
+        ends_at: 1000,
+    });
+    let result = client.try_update_price_phases(&event_id, &tier_id, &unordered);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidPriceSchedule)));
+
+    // Clearing the phases falls back to the regular price
+    client.update_price_phases(&event_id, &tier_id, &Vec::new(&env));
+    let tier = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id)
+        .unwrap();
+    assert_eq!(tier.active_price(500), (0, regular_price));
+}
+
+#[test]
+fn test_register_event_invalid_price_phases() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let event_id = String::from_str(&env, "tiered_event");
+    register_tiered_event(&env, &client, &event_id, 0);
+
+    // Phases must end in strictly increasing order
+    let mut price_phases = Vec::new(&env);
+    price_phases.push_back(PricePhase {
+        name: String::from_str(&env, "Early"),
+        price: 75,
+        ends_at: 2000,
+    });
+    price_phases.push_back(PricePhase {
+        name: String::from_str(&env, "Super Early"),
+        price: 50,
+        ends_at: 1000,
+    });
+    let mut tier = TicketTier {
+        name: String::from_str(&env, "VIP"),
+        price: 100,
+        price_phases,
+        tier_limit: 10,
+        current_sold: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        is_refundable: true,
+        refund_policy: RefundPolicy::full_refund(&env),
+        is_retired: false,
+    };
+    let result = client.try_add_tier(&event_id, &String::from_str(&env, "vip"), &tier);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidPriceSchedule)));
+
+    tier.price_phases = Vec::new(&env);
+    tier.price_phases.push_back(PricePhase {
+        name: String::from_str(&env, "Early"),
+        price: -1,
+        ends_at: 2000,
+    });
+    let result = client.try_add_tier(&event_id, &String::from_str(&env, "vip"), &tier);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidPriceSchedule)));
+}
+
+#[test]
+fn test_refund_policy_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let event_id = String::from_str(&env, "tiered_event");
+    register_tiered_event(&env, &client, &event_id, 100);
+
+    let day = 86400u64;
+    let window = |min_seconds_before_start: u64, refund_bps: u32| RefundWindow {
+        min_seconds_before_start,
+        refund_bps,
+    };
+    let tier_with = |windows: Vec<RefundWindow>, processing_fee: i128| TicketTier {
+        name: String::from_str(&env, "Flex"),
+        price: 10000000,
+        price_phases: Vec::new(&env),
+        tier_limit: 10,
+        current_sold: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        is_refundable: true,
+        refund_policy: RefundPolicy {
+            windows,
+            processing_fee,
+        },
+        is_retired: false,
+    };
+
+    // 100% until 14 days before, 50% until 48 hours before
+    let valid = tier_with(
+        vec![&env, window(14 * day, 10000), window(2 * day, 5000)],
+        1000000,
+    );
+    client.add_tier(&event_id, &String::from_str(&env, "flex"), &valid);
+    let policy = valid.refund_policy;
+    assert_eq!(policy.refund_bps(20 * day), 10000);
+    assert_eq!(policy.refund_bps(3 * day), 5000);
+    assert_eq!(policy.refund_bps(day), 0);
+
+    let unordered = tier_with(
+        vec![&env, window(2 * day, 5000), window(14 * day, 10000)],
+        0,
+    );
+    let over_full = tier_with(vec![&env, window(day, 10001)], 0);
+    let negative_fee = tier_with(vec![&env, window(day, 10000)], -1);
+    for (tier_id, tier) in [("a", unordered), ("b", over_full), ("c", negative_fee)] {
+        let result = client.try_add_tier(&event_id, &String::from_str(&env, tier_id), &tier);
+        assert_eq!(result, Err(Ok(EventRegistryError::InvalidRefundPolicy)));
+    }
+}
+
+#[test]
+fn test_revenue_split_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+
+    let organizer = Address::generate(&env);
+    let promoter = Address::generate(&env);
+    let venue = Address::generate(&env);
+    let split = |payee: &Address, bps: u32| RevenueSplit {
+        payee: payee.clone(),
+        bps,
+    };
+    let args_with = |event_id: &str, splits: Vec<RevenueSplit>| EventRegistrationArgs {
+        event_id: String::from_str(&env, event_id),
+        organizer_address: organizer.clone(),
+        payment_address: organizer.clone(),
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 100,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: Some(splits),
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers: Map::new(&env),
+    };
+
+    let valid = vec![&env, split(&promoter, 7000), split(&venue, 3000)];
+    client.register_event(&args_with("split_event", valid.clone()));
+    let event = client
+        .get_event(&String::from_str(&env, "split_event"))
+        .unwrap();
+    assert_eq!(event.revenue_splits, Some(valid));
+
+    let short = vec![&env, split(&promoter, 7000), split(&venue, 2999)];
+    let over = vec![&env, split(&promoter, 7000), split(&venue, 3001)];
+    let duplicate = vec![&env, split(&promoter, 5000), split(&promoter, 5000)];
+    let empty_share = vec![&env, split(&promoter, 10000), split(&venue, 0)];
+    let none = Vec::new(&env);
+    for (event_id, splits) in [
+        ("a", short),
+        ("b", over),
+        ("c", duplicate),
+        ("d", empty_share),
+        ("e", none),
+    ] {
+        let result = client.try_register_event(&args_with(event_id, splits));
+        assert_eq!(result, Err(Ok(EventRegistryError::InvalidRevenueSplit)));
+    }
+}
+
+#[test]
+fn test_minimum_sales_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    let organizer = Address::generate(&env);
+    let tier_id = String::from_str(&env, "general");
+    let args = |event_id: &str, min_sales_target: i128, sales_target_deadline: u64| {
+        let mut tiers = Map::new(&env);
+        tiers.set(
+            tier_id.clone(),
+            TicketTier {
+                name: String::from_str(&env, "General"),
+                price: 5000000,
+                price_phases: Vec::new(&env),
+                tier_limit: 10,
+                current_sold: 0,
+                sales_start_time: 0,
+                sales_end_time: 0,
+                is_refundable: false,
+                refund_policy: RefundPolicy::full_refund(&env),
+                is_retired: false,
+            },
+        );
+        EventRegistrationArgs {
+            event_id: String::from_str(&env, event_id),
+            organizer_address: organizer.clone(),
+            payment_address: organizer.clone(),
+            metadata_cid: String::from_str(
+                &env,
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
+            max_supply: 10,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            milestone_plan: None,
+            revenue_splits: None,
+            min_sales_target,
+            sales_target_deadline,
+            draft: false,
+            tiers,
+        }
+    };
+
+    // Targets need a deadline and must fit within the supply
+    for (min_sales_target, sales_target_deadline) in [(-1, 1000), (2, 0), (11, 1000)] {
+        let result =
+            client.try_register_event(&args("bad", min_sales_target, sales_target_deadline));
+        assert_eq!(result, Err(Ok(EventRegistryError::InvalidSalesTarget)));
+    }
+    // The deadline must fall before the event ends
+    let mut late = args("bad", 2, 1000);
+    late.end_time = 1000;
+    let result = client.try_register_event(&late);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidSalesTarget)));
+
+    let met = String::from_str(&env, "met");
+    let missed = String::from_str(&env, "missed");
+    let released = String::from_str(&env, "released");
+    client.register_event(&args("met", 2, 1000));
+    client.register_event(&args("missed", 3, 1000));
+    client.register_event(&args("released", 2, 1000));
+
+    // Reaching the target before the deadline does not settle it yet
+    client.increment_inventory(&met, &tier_id, &2);
+    client.increment_inventory(&missed, &tier_id, &2);
+    client.increment_inventory(&released, &tier_id, &2);
+    let event = client.get_event(&met).unwrap();
+    assert!(event.sales_target_pending(env.ledger().timestamp()));
+    assert!(!event.sales_target_met);
+
+    let result = client.try_cancel_unmet_event(&missed);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesTargetNotMissed)));
+
+    // Tickets returned before the deadline no longer count towards it
+    client.decrement_inventory(&released, &tier_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    // A met target is recorded and survives later refunds; a missed one stops
+    // sales and lets anyone cancel
+    client.increment_inventory(&met, &tier_id, &1);
+    assert!(client.get_event(&met).unwrap().sales_target_met);
+    client.decrement_inventory(&met, &tier_id);
+    client.decrement_inventory(&met, &tier_id);
+    let event = client.get_event(&met).unwrap();
+    assert!(!event.sales_target_pending(env.ledger().timestamp()));
+    let result = client.try_cancel_unmet_event(&met);
+    assert_eq!(result, Err(Ok(EventRegistryError::SalesTargetNotMissed)));
+
+    let result = client.try_increment_inventory(&released, &tier_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+    client.cancel_unmet_event(&released);
+
+    let result = client.try_increment_inventory(&missed, &tier_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+    client.cancel_unmet_event(&missed);
+    assert_eq!(
+        client.get_event(&missed).unwrap().status,
+        EventStatus::Cancelled
+    );
+    let result = client.try_cancel_unmet_event(&missed);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_draft_event_published_later() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    let organizer = Address::generate(&env);
+    let event_id = String::from_str(&env, "draft_event");
+    let tier_id = String::from_str(&env, "general");
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        tier_id.clone(),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5000000,
+            price_phases: Vec::new(&env),
+            tier_limit: 10,
+            current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: false,
+        },
+    );
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        organizer_address: organizer.clone(),
+        payment_address: organizer,
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 10,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: true,
+        tiers,
+    });
+    assert_eq!(
+        client.get_event(&event_id).unwrap().status,
+        EventStatus::Draft
+    );
+
+    // Drafts are hidden from buyers until published
+    let result = client.try_increment_inventory(&event_id, &tier_id, &1);
+    assert_eq!(result, Err(Ok(EventRegistryError::EventInactive)));
+
+    client.update_event_status(&event_id, &EventStatus::OnSale);
+    client.increment_inventory(&event_id, &tier_id, &1);
+    assert_eq!(client.get_event(&event_id).unwrap().current_supply, 1);
+
+    let result = client.try_update_event_status(&event_id, &EventStatus::Draft);
+    assert_eq!(result, Err(Ok(EventRegistryError::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_registered_tiers_start_empty() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EventRegistry, ());
+    let client = EventRegistryClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &500);
+    client.set_ticket_payment_contract(&Address::generate(&env));
+
+    let organizer = Address::generate(&env);
+    let event_id = String::from_str(&env, "prefilled_event");
+    let tier_id = String::from_str(&env, "general");
+    let mut tiers = Map::new(&env);
+    tiers.set(
+        tier_id.clone(),
+        TicketTier {
+            name: String::from_str(&env, "General"),
+            price: 5000000,
+            price_phases: Vec::new(&env),
+            tier_limit: 10,
+            current_sold: 10,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            is_refundable: true,
+            refund_policy: RefundPolicy::full_refund(&env),
+            is_retired: true,
+        },
+    );
+    client.register_event(&EventRegistrationArgs {
+        event_id: event_id.clone(),
+        organizer_address: organizer.clone(),
+        payment_address: organizer,
+        metadata_cid: String::from_str(
+            &env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 10,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits: None,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers,
+    });
+
+    // The caller cannot register a tier as already sold out or retired
+    let tier = client
+        .get_event(&event_id)
+        .unwrap()
+        .tiers
+        .get(tier_id.clone())
+        .unwrap();
+    assert_eq!(tier.current_sold, 0);
+    assert!(!tier.is_retired);
+
+    client.increment_inventory(&event_id, &tier_id, &10);
+    let event = client.get_event(&event_id).unwrap();
+    assert_eq!(event.current_supply, 10);
+    assert_eq!(event.tiers.get(tier_id).unwrap().current_sold, 10);
+}
@@ -1,83 +1,9 @@
-use soroban_sdk::{contracttype, Address, Map, String, Vec};
+use soroban_sdk::{contracttype, Address, String};
 
-/// Represents a ticket tier with its own pricing and supply
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct TicketTier {
-    /// Name of the tier (e.g., "General", "VIP", "Reserved")
-    pub name: String,
-    /// Price for this tier in stroops
-    pub price: i128,
-    /// Maximum tickets available for this tier
-    pub tier_limit: i128,
-    /// Current number of tickets sold for this tier
-    pub current_sold: i128,
-    /// Indicates whether tickets in this tier can be refunded by the buyer
-    pub is_refundable: bool,
-}
-
-/// Represents an early revenue release milestone.
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Milestone {
-    /// The number of tickets sold to reach this milestone
-    pub sales_threshold: i128,
-    /// Percentage of the available revenue to release (in basis points, 10000 = 100%)
-    pub release_percent: u32,
-}
-
-/// Represents information about an event in the registry.
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct EventInfo {
-    /// Unique identifier for the event
-    pub event_id: String,
-    /// The wallet address of the event organizer
-    pub organizer_address: Address,
-    /// The address where payments for this event should be routed
-    pub payment_address: Address,
-    /// The percentage fee taken by the platform (e.g., 5 for 5%)
-    pub platform_fee_percent: u32,
-    /// Whether the event is currently active and accepting payments
-    pub is_active: bool,
-    /// Timestamp when the event was created
-    pub created_at: u64,
-    /// IPFS Content Identifier storing rich metadata details
-    pub metadata_cid: String,
-    /// Maximum number of tickets available for this event (0 = unlimited)
-    pub max_supply: i128,
-    /// Current number of tickets that have been successfully purchased
-    pub current_supply: i128,
-    /// Optional milestone plan for early revenue release
-    pub milestone_plan: Option<Vec<Milestone>>,
-    /// Map of tier_id to TicketTier for multi-tiered pricing
-    pub tiers: Map<String, TicketTier>,
-}
-
-/// Payment information for an event
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct PaymentInfo {
-    /// The address where payments for this event should be routed
-    pub payment_address: Address,
-    /// The percentage fee taken by the platform
-    pub platform_fee_percent: u32,
-    /// Map of tier_id to TicketTier for multi-tiered pricing
-    pub tiers: Map<String, TicketTier>,
-}
-
-/// Arguments required to register a new event
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct EventRegistrationArgs {
-    pub event_id: String,
-    pub organizer_address: Address,
-    pub payment_address: Address,
-    pub metadata_cid: String,
-    pub max_supply: i128,
-    pub milestone_plan: Option<Vec<Milestone>>,
-    pub tiers: Map<String, TicketTier>,
-}
+pub use agora_types::event_registry::{
+    EventInfo, EventRegistrationArgs, EventRole, EventStatus, Milestone, PaymentInfo, PricePhase,
+    RefundPolicy, RefundWindow, RevenueSplit, TicketTier,
+};
 
 /// Storage keys for the Event Registry contract.
 #[contracttype]
@@ -96,4 +22,10 @@ pub enum DataKey {
     OrganizerEvents(Address),
     /// The authorized TicketPayment contract address for inventory updates
     TicketPaymentContract,
+    /// Delegated staff role for an event: (event_id, role, account) (Persistent)
+    EventRole(String, EventRole, Address),
+    /// Mapping of fee plan ID to its platform fee in basis points (Persistent)
+    FeePlan(String),
+    /// Mapping of organizer_address to the fee plan ID assigned to it (Persistent)
+    OrganizerFeePlan(Address),
 }
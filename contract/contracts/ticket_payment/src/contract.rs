@@ -1,84 +1,39 @@
-extern crate alloc;
 use crate::storage::{
-    add_payment_to_buyer_index, add_token_to_whitelist, get_admin, get_bulk_refund_index,
-    get_event_balance, get_event_payments, get_event_registry, get_payment, get_platform_wallet,
-    get_transfer_fee, is_initialized, is_token_whitelisted, remove_payment_from_buyer_index,
-    remove_token_from_whitelist, set_admin, set_bulk_refund_index, set_event_registry,
-    set_initialized, set_platform_wallet, set_transfer_fee, set_usdc_token, store_payment,
-    update_event_balance, update_payment_status,
+    add_confirmer, add_live_tickets, add_payment_to_buyer_index, add_token_to_whitelist, get_admin,
+    get_approval, get_bulk_refund_index, get_default_confirmation_window, get_escrow_events,
+    get_event_balance, get_event_confirmation_window, get_event_payments, get_event_registry,
+    get_expiry_sweep_index, get_fee_mode, get_listing, get_live_tickets, get_order, get_payment,
+    get_pending_escrow, get_platform_fee_ledger, get_platform_wallet, get_price_phase,
+    get_resale_config, get_revenue_claimed, get_sales_escrow, get_settlement_config,
+    get_solvency_audit, get_transfer_fee, has_payment, is_confirmer, is_initialized,
+    is_token_whitelisted, record_pending_escrow, record_platform_fee,
+    record_platform_fee_withdrawal, record_sales_escrow, remove_approval, remove_confirmer,
+    remove_listing, remove_live_ticket, remove_payment_from_buyer_index, remove_solvency_audit,
+    remove_token_from_whitelist, set_admin, set_approval, set_bulk_refund_index,
+    set_default_confirmation_window, set_event_confirmation_window, set_event_registry,
+    set_expiry_sweep_index, set_fee_mode, set_initialized, set_listing, set_platform_wallet,
+    set_price_phase, set_resale_config, set_revenue_claimed, set_settlement_config,
+    set_solvency_audit, set_transfer_fee, set_usdc_token, store_order, store_payment,
+    update_event_balance,
+};
+use crate::types::{
+    EscrowReconciliation, EventBalance, FeeMode, Listing, Order, Payment, PaymentStatus,
+    PlatformFeeLedger, PlatformFeeSweep, ResaleConfig, SalesEscrow, SettlementConfig,
+    SolvencyAudit, SolvencyReport, TicketApproval,
 };
-use crate::types::{Payment, PaymentStatus};
 use crate::{
     error::TicketPaymentError,
     events::{
-        AgoraEvent, BulkRefundProcessedEvent, ContractUpgraded, InitializationEvent,
-        PaymentProcessedEvent, PaymentStatusChangedEvent, PriceSwitchedEvent,
-        TicketTransferredEvent,
+        AgoraEvent, BulkRefundProcessedEvent, ConfirmerUpdatedEvent, ContractUpgraded,
+        EventSettledEvent, InitializationEvent, ListingCancelledEvent, PaymentProcessedEvent,
+        PaymentStatusChangedEvent, PlatformFeesSweptEvent, PriceSwitchedEvent, TicketApprovedEvent,
+        TicketCheckedInEvent, TicketListedEvent, TicketResoldEvent, TicketTransferredEvent,
     },
 };
-use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, String, Symbol};
-
-// Event Registry interface
-pub mod event_registry {
-    use soroban_sdk::{contractclient, Address, Env, String};
-
-    #[soroban_sdk::contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub struct PaymentInfo {
-        pub payment_address: Address,
-        pub platform_fee_percent: u32,
-    }
-
-    #[soroban_sdk::contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub struct EventInventory {
-        pub current_supply: i128,
-        pub max_supply: i128,
-    }
-
-    #[contractclient(name = "Client")]
-    pub trait EventRegistryInterface {
-        fn get_event_payment_info(env: Env, event_id: String) -> PaymentInfo;
-        fn get_event(env: Env, event_id: String) -> Option<EventInfo>;
-        fn increment_inventory(env: Env, event_id: String, tier_id: String, quantity: u32);
-        fn decrement_inventory(env: Env, event_id: String, tier_id: String);
-    }
-
-    #[soroban_sdk::contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub struct TicketTier {
-        pub name: String,
-        pub price: i128,
-        pub early_bird_price: i128,
-        pub early_bird_deadline: u64,
-        pub tier_limit: i128,
-        pub current_sold: i128,
-        pub is_refundable: bool,
-    }
-
-    #[soroban_sdk::contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub struct Milestone {
-        pub sales_threshold: i128,
-        pub release_percent: u32,
-    }
-
-    #[soroban_sdk::contracttype]
-    #[derive(Clone, Debug, Eq, PartialEq)]
-    pub struct EventInfo {
-        pub event_id: String,
-        pub organizer_address: Address,
-        pub payment_address: Address,
-        pub platform_fee_percent: u32,
-        pub is_active: bool,
-        pub created_at: u64,
-        pub metadata_cid: String,
-        pub max_supply: i128,
-        pub current_supply: i128,
-        pub milestone_plan: Option<soroban_sdk::Vec<Milestone>>,
-        pub tiers: soroban_sdk::Map<String, TicketTier>,
-    }
-}
+use soroban_sdk::{contract, contractimpl, token, Address, BytesN, Env, String, Vec};
+
+// Event Registry interface, shared with the registry through `agora_types`
+pub use agora_types::event_registry;
 
 #[contract]
 pub struct TicketPaymentContract;
@@ -162,20 +117,29 @@ impl TicketPaymentContract {
     }
 
     /// Processes a payment for an event ticket.
+    ///
+    /// `payer_address` authorizes and funds the purchase and receives any
+    /// refund; the tickets are issued to `buyer_address`, which may be the
+    /// payer itself or someone they are buying for.
+    ///
+    /// `amount` is what the payer is charged per ticket: the tier's active
+    /// price, plus the platform fee when the event passes its fee to buyers
+    /// (see `get_ticket_price`).
     pub fn process_payment(
         env: Env,
         payment_id: String,
         event_id: String,
         ticket_tier_id: String,
+        payer_address: Address,
         buyer_address: Address,
         token_address: Address,
-        amount: i128, // price for ONE ticket
+        amount: i128, // charged for ONE ticket
         quantity: u32,
     ) -> Result<String, TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        buyer_address.require_auth();
+        payer_address.require_auth();
 
         if amount <= 0 {
             panic!("Amount must be positive");
@@ -189,6 +153,9 @@ impl TicketPaymentContract {
             return Err(TicketPaymentError::TokenNotWhitelisted);
         }
 
+        // Derive every ticket ID up front so a collision fails before funds move
+        let ticket_ids = derive_ticket_ids(&env, &payment_id, quantity)?;
+
         let total_amount = amount
             .checked_mul(quantity as i128)
             .ok_or(TicketPaymentError::ArithmeticError)?;
@@ -203,7 +170,9 @@ impl TicketPaymentContract {
             _ => return Err(TicketPaymentError::EventNotFound),
         };
 
-        if !event_info.is_active {
+        if event_info.status != event_registry::EventStatus::OnSale
+            || is_cancelled(&env, &event_info)
+        {
             return Err(TicketPaymentError::EventInactive);
         }
 
@@ -213,39 +182,44 @@ impl TicketPaymentContract {
             .ok_or(TicketPaymentError::TierNotFound)?;
 
         let current_time = env.ledger().timestamp();
-        let mut active_price = tier.price;
-
-        if tier.early_bird_deadline > 0 && current_time <= tier.early_bird_deadline {
-            active_price = tier.early_bird_price;
-        }
+        let (phase_index, active_price) = tier.active_price(current_time);
+        let fee_mode = get_fee_mode(&env, event_id.clone());
 
-        if amount != active_price {
+        if amount != checkout_price(active_price, event_info.platform_fee_percent, fee_mode)? {
             return Err(TicketPaymentError::InvalidPrice);
         }
 
-        // Check if we just transitioned from early bird to standard
-        if tier.early_bird_deadline > 0 && current_time > tier.early_bird_deadline {
-            let switch_key = Symbol::new(
-                &env,
-                alloc::format!("ps_{}_{}", event_id, ticket_tier_id).as_str(),
-            );
-            let has_switched: bool = env.storage().persistent().get(&switch_key).unwrap_or(false);
-            if !has_switched {
-                env.storage().persistent().set(&switch_key, &true);
+        // Announce every price phase once, including phases that ended
+        // without a sale since the last purchase
+        let announced_phase = get_price_phase(&env, event_id.clone(), ticket_tier_id.clone());
+        if announced_phase < phase_index {
+            set_price_phase(&env, event_id.clone(), ticket_tier_id.clone(), phase_index);
+            for index in announced_phase + 1..=phase_index {
+                let new_price = tier
+                    .price_phases
+                    .get(index)
+                    .map_or(tier.price, |phase| phase.price);
                 env.events().publish(
                     (AgoraEvent::PriceSwitched,),
                     PriceSwitchedEvent {
                         event_id: event_id.clone(),
                         tier_id: ticket_tier_id.clone(),
-                        new_price: tier.price,
+                        phase_index: index,
+                        new_price,
                         timestamp: current_time,
                     },
                 );
             }
         }
 
-        // 2. Calculate platform fee (platform_fee_percent is in bps, 10000 = 100%)
-        let total_platform_fee = (total_amount * event_info.platform_fee_percent as i128) / 10000;
+        // 2. Calculate platform fee (platform_fee_percent is in bps, 10000 = 100%).
+        // A fee passed to the buyer is whatever they pay above the tier price.
+        let total_platform_fee = match fee_mode {
+            FeeMode::OrganizerAbsorbs => {
+                (total_amount * event_info.platform_fee_percent as i128) / 10000
+            }
+            FeeMode::BuyerPays => (amount - active_price) * quantity as i128,
+        };
         let total_organizer_amount = total_amount - total_platform_fee;
 
         // 3. Transfer tokens to contract (escrow)
@@ -253,7 +227,7 @@ impl TicketPaymentContract {
         let contract_address = env.current_contract_address();
 
         // Verify allowance
-        let allowance = token_client.allowance(&buyer_address, &contract_address);
+        let allowance = token_client.allowance(&payer_address, &contract_address);
         if allowance < total_amount {
             return Err(TicketPaymentError::InsufficientAllowance);
         }
@@ -264,7 +238,7 @@ impl TicketPaymentContract {
         // Transfer full amount to contract
         token_client.transfer_from(
             &contract_address,
-            &buyer_address,
+            &payer_address,
             &contract_address,
             &total_amount,
         );
@@ -279,6 +253,21 @@ impl TicketPaymentContract {
         update_event_balance(
             &env,
             event_id.clone(),
+            token_address.clone(),
+            total_organizer_amount,
+            total_platform_fee,
+        );
+        record_sales_escrow(
+            &env,
+            event_id.clone(),
+            token_address.clone(),
+            total_organizer_amount,
+            total_platform_fee,
+        );
+        record_pending_escrow(
+            &env,
+            event_id.clone(),
+            token_address.clone(),
             total_organizer_amount,
             total_platform_fee,
         );
@@ -286,42 +275,56 @@ impl TicketPaymentContract {
         // 5. Increment inventory after successful payment
         registry_client.increment_inventory(&event_id, &ticket_tier_id, &quantity);
 
-        // 6. Create payment records for each individual ticket
-        let platform_fee_per_ticket = total_platform_fee / quantity as i128;
-        let organizer_amount_per_ticket = total_organizer_amount / quantity as i128;
+        // 6. Create payment records for each individual ticket. The fee's
+        // rounding remainder goes to the first tickets, one stroop each, so the
+        // records add up to exactly what was escrowed.
+        let fee_per_ticket = total_platform_fee / quantity as i128;
+        let fee_remainder = total_platform_fee % quantity as i128;
 
-        for i in 0..quantity {
-            // Re-initialize the sub_payment_id with a unique ID for each ticket in a batch.
-            // Since concatenation is complex in Soroban no_std, we use a match for common indices.
-            let sub_payment_id = if quantity == 1 {
-                payment_id.clone()
+        for (index, ticket_id) in ticket_ids.iter().enumerate() {
+            let platform_fee = if (index as i128) < fee_remainder {
+                fee_per_ticket + 1
             } else {
-                match i {
-                    0 => String::from_str(&env, "p-0"),
-                    1 => String::from_str(&env, "p-1"),
-                    2 => String::from_str(&env, "p-2"),
-                    3 => String::from_str(&env, "p-3"),
-                    4 => String::from_str(&env, "p-4"),
-                    _ => String::from_str(&env, "p-many"),
-                }
+                fee_per_ticket
             };
-
             let payment = Payment {
-                payment_id: sub_payment_id.clone(),
+                payment_id: ticket_id,
+                order_id: payment_id.clone(),
                 event_id: event_id.clone(),
+                payer_address: payer_address.clone(),
                 buyer_address: buyer_address.clone(),
                 ticket_tier_id: ticket_tier_id.clone(),
+                token_address: token_address.clone(),
                 amount,
-                platform_fee: platform_fee_per_ticket,
-                organizer_amount: organizer_amount_per_ticket,
+                platform_fee,
+                platform_fee_bps: event_info.platform_fee_percent,
+                organizer_amount: amount - platform_fee,
+                refunded_amount: 0,
+                refunded_fee: 0,
                 status: PaymentStatus::Pending,
                 transaction_hash: String::from_str(&env, ""),
                 created_at: env.ledger().timestamp(),
                 confirmed_at: None,
+                checked_in_at: None,
             };
 
             store_payment(&env, payment);
         }
+        add_live_tickets(&env, event_id.clone(), quantity);
+
+        store_order(
+            &env,
+            &Order {
+                order_id: payment_id.clone(),
+                event_id: event_id.clone(),
+                payer_address: payer_address.clone(),
+                buyer_address: buyer_address.clone(),
+                ticket_tier_id: ticket_tier_id.clone(),
+                ticket_ids,
+                total_amount,
+                created_at: env.ledger().timestamp(),
+            },
+        );
 
         // 7. Emit payment event
         env.events().publish(
@@ -329,9 +332,11 @@ impl TicketPaymentContract {
             PaymentProcessedEvent {
                 payment_id: payment_id.clone(),
                 event_id: event_id.clone(),
+                payer_address: payer_address.clone(),
                 buyer_address: buyer_address.clone(),
                 amount: total_amount,
                 platform_fee: total_platform_fee,
+                organizer_amount: total_organizer_amount,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -339,25 +344,80 @@ impl TicketPaymentContract {
         Ok(payment_id)
     }
 
-    /// Confirms a payment after backend verification.
-    pub fn confirm_payment(env: Env, payment_id: String, transaction_hash: String) {
+    /// Adds an address allowed to confirm payments (e.g., the backend signer).
+    pub fn add_confirmer(env: Env, confirmer: Address) {
+        let admin = get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+        add_confirmer(&env, &confirmer);
+
+        env.events().publish(
+            (AgoraEvent::ConfirmerAdded,),
+            ConfirmerUpdatedEvent {
+                confirmer,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Revokes an address's permission to confirm payments.
+    pub fn remove_confirmer(env: Env, confirmer: Address) {
+        let admin = get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+        remove_confirmer(&env, &confirmer);
+
+        env.events().publish(
+            (AgoraEvent::ConfirmerRemoved,),
+            ConfirmerUpdatedEvent {
+                confirmer,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    pub fn is_confirmer(env: Env, confirmer: Address) -> bool {
+        is_confirmer(&env, &confirmer)
+    }
+
+    /// Confirms a pending payment after backend verification.
+    ///
+    /// `confirmer` must be in the admin-managed confirmer set and sign the call.
+    pub fn confirm_payment(
+        env: Env,
+        payment_id: String,
+        transaction_hash: String,
+        confirmer: Address,
+    ) -> Result<(), TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
         }
-        // In a real scenario, this would be restricted to a specific backend/admin address.
-        update_payment_status(
-            &env,
-            payment_id.clone(),
-            PaymentStatus::Confirmed,
-            Some(env.ledger().timestamp()),
-        );
+        confirmer.require_auth();
 
-        // Update the transaction hash
-        if let Some(mut payment) = get_payment(&env, payment_id.clone()) {
-            payment.transaction_hash = transaction_hash.clone();
-            store_payment(&env, payment);
+        if !is_confirmer(&env, &confirmer) {
+            return Err(TicketPaymentError::Unauthorized);
+        }
+
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+        if payment.status != PaymentStatus::Pending {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        if is_expired(&env, &payment) {
+            return Err(TicketPaymentError::PaymentExpired);
         }
 
+        payment.status = PaymentStatus::Confirmed;
+        payment.confirmed_at = Some(env.ledger().timestamp());
+        payment.transaction_hash = transaction_hash.clone();
+        record_pending_escrow(
+            &env,
+            payment.event_id.clone(),
+            payment.token_address.clone(),
+            -payment.organizer_amount,
+            -payment.platform_fee,
+        );
+        store_payment(&env, payment);
+
         // Emit confirmation event
         env.events().publish(
             (AgoraEvent::PaymentStatusChanged,),
@@ -369,6 +429,8 @@ impl TicketPaymentContract {
                 timestamp: env.ledger().timestamp(),
             },
         );
+
+        Ok(())
     }
 
     pub fn request_guest_refund(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
@@ -393,21 +455,51 @@ impl TicketPaymentContract {
             _ => return Err(TicketPaymentError::EventNotFound),
         };
 
+        // A checked-in ticket has been used, unless its event was cancelled since
+        if payment.status == PaymentStatus::CheckedIn && !is_cancelled(&env, &event_info) {
+            return Err(TicketPaymentError::TicketAlreadyCheckedIn);
+        }
+
         let tier = event_info
             .tiers
             .get(payment.ticket_tier_id.clone())
             .ok_or(TicketPaymentError::TierNotFound)?;
 
-        // Check if refundable or if EVENT IS CANCELLED (is_active == false)
-        if !tier.is_refundable && event_info.is_active {
+        // A cancelled event, including one that missed its sales target, refunds
+        // every ticket in full; otherwise the tier decides.
+        // Pausing sales or closing them does not change a ticket's refund rules.
+        let (refund_amount, processing_fee) = if is_cancelled(&env, &event_info) {
+            (payment.amount, 0)
+        } else if !tier.is_refundable {
             return Err(TicketPaymentError::TicketNotRefundable);
-        }
+        } else {
+            let refund_amount = policy_refund_amount(
+                &payment,
+                &tier,
+                event_info.start_time,
+                env.ledger().timestamp(),
+            )?;
+            (refund_amount, tier.refund_policy.processing_fee)
+        };
+
+        // Take the refund out of escrow
+        let refunded_fee = debit_escrow(&env, &payment, refund_amount, processing_fee)?;
 
         // Return ticket to inventory using the authorized contract interface
         registry_client.decrement_inventory(&payment.event_id, &payment.ticket_tier_id);
+        remove_live_ticket(&env, payment.event_id.clone());
+
+        // Send the refund to the payer, in the token the ticket was paid with
+        token::Client::new(&env, &payment.token_address).transfer(
+            &env.current_contract_address(),
+            &payment.payer_address,
+            &refund_amount,
+        );
 
         let old_status = payment.status.clone();
         payment.status = PaymentStatus::Refunded;
+        payment.refunded_amount = refund_amount;
+        payment.refunded_fee = refunded_fee;
         payment.confirmed_at = Some(env.ledger().timestamp());
 
         store_payment(&env, payment);
@@ -427,85 +519,228 @@ impl TicketPaymentContract {
         Ok(())
     }
 
+    /// Sets how long, in seconds, a payment may stay pending before anyone can
+    /// expire it. Applies to events without their own window; 0 disables
+    /// expiry. Only callable by the administrator.
+    pub fn set_confirmation_window(env: Env, seconds: u64) {
+        let admin = get_admin(&env).expect("Admin not set");
+        admin.require_auth();
+        set_default_confirmation_window(&env, seconds);
+    }
+
+    /// Overrides the confirmation window for one event. Only the organizer can
+    /// call this.
+    pub fn set_event_confirmation_window(
+        env: Env,
+        event_id: String,
+        seconds: u64,
+    ) -> Result<(), TicketPaymentError> {
+        let event_info = get_event_info(&env, &event_id)?;
+        event_info.organizer_address.require_auth();
+        set_event_confirmation_window(&env, event_id, seconds);
+        Ok(())
+    }
+
+    /// Returns the confirmation window that applies to an event, in seconds
+    /// (0 means pending payments never expire).
+    pub fn get_confirmation_window(env: Env, event_id: String) -> u64 {
+        confirmation_window(&env, event_id)
+    }
+
+    /// Fails a payment that stayed pending past its confirmation deadline.
+    ///
+    /// Anyone can call this. The escrowed amount goes back to the payer and the
+    /// ticket returns to the registry's inventory.
+    pub fn expire_payment(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        if payment.status != PaymentStatus::Pending {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        if !is_expired(&env, &payment) {
+            return Err(TicketPaymentError::PaymentNotExpired);
+        }
+
+        expire_pending_payment(&env, payment_id, payment)
+    }
+
+    /// Expires stale pending payments of an event, returning how many were
+    /// expired. Anyone can call this.
+    ///
+    /// Reads at most `limit` payments, resuming where the previous sweep of
+    /// the event stopped. Payments are swept in purchase order, so the sweep
+    /// stops at the first pending payment that has not expired yet. A payment
+    /// that cannot be expired is skipped; `expire_payment` can retry it.
+    pub fn sweep_expired(
+        env: Env,
+        event_id: String,
+        limit: u32,
+    ) -> Result<u32, TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+
+        let payment_ids = get_event_payments(&env, event_id.clone());
+        let start_index = get_expiry_sweep_index(&env, event_id.clone());
+        let end_index = core::cmp::min(start_index.saturating_add(limit), payment_ids.len());
+
+        let mut index = start_index;
+        let mut expired = 0;
+        while index < end_index {
+            let payment_id = payment_ids.get(index).unwrap();
+            if let Some(payment) = get_payment(&env, payment_id.clone()) {
+                if payment.status == PaymentStatus::Pending {
+                    if !is_expired(&env, &payment) {
+                        break;
+                    }
+                    if expire_pending_payment(&env, payment_id, payment).is_ok() {
+                        expired += 1;
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        set_expiry_sweep_index(&env, event_id, index);
+        Ok(expired)
+    }
+
     /// Returns the status and details of a payment.
     pub fn get_payment_status(env: Env, payment_id: String) -> Option<Payment> {
         get_payment(&env, payment_id)
     }
 
-    /// Returns the escrowed balance for an event.
-    pub fn get_event_escrow_balance(env: Env, event_id: String) -> crate::types::EventBalance {
-        get_event_balance(&env, event_id)
+    /// Returns the order created by a `process_payment` call, listing its ticket IDs.
+    pub fn get_order(env: Env, order_id: String) -> Option<Order> {
+        get_order(&env, order_id)
+    }
+
+    /// Returns the escrowed balance an event holds in `token_address`.
+    pub fn get_event_escrow_balance(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+    ) -> crate::types::EventBalance {
+        get_event_balance(&env, event_id, token_address)
     }
 
-    /// Withdraw organizer funds from escrow.
+    /// Withdraw organizer funds held in `token_address` from escrow.
+    ///
+    /// Each token is accounted separately, so only revenue paid in that token
+    /// can be withdrawn in it. Until the event settles, milestones limit the
+    /// share that can be withdrawn and the settlement holdback stays in escrow.
+    /// If the event splits its revenue, every payee is paid their share of
+    /// the withdrawal. Nothing can be withdrawn while the event's minimum
+    /// sales target is unmet, and escrow of payments still pending stays until
+    /// they are confirmed, since an expired payment is refunded in full.
+    /// Returns the total paid out.
     pub fn withdraw_organizer_funds(
         env: Env,
         event_id: String,
         token_address: Address,
     ) -> Result<i128, TicketPaymentError> {
-        let event_registry_addr = get_event_registry(&env);
-        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
-        let event_info = registry_client
-            .try_get_event(&event_id)
-            .ok()
-            .and_then(|r| r.ok())
-            .flatten()
-            .ok_or(TicketPaymentError::EventNotFound)?;
-
+        let event_info = get_event_info(&env, &event_id)?;
         event_info.organizer_address.require_auth();
 
-        let balance = get_event_balance(&env, event_id.clone());
-        let total_revenue = balance.organizer_amount + balance.total_withdrawn;
-        if total_revenue == 0 {
-            return Ok(0);
+        // Escrow of a cancelled event is reserved for buyer refunds
+        if is_cancelled(&env, &event_info) {
+            return Err(TicketPaymentError::EventCancelled);
         }
-
-        let mut release_percent = 10000u32;
-        if let Some(milestones) = event_info.milestone_plan {
-            let mut highest_met = 0u32;
-            for milestone in milestones.iter() {
-                if event_info.current_supply >= milestone.sales_threshold
-                    && milestone.release_percent > highest_met
-                {
-                    highest_met = milestone.release_percent;
-                }
-            }
-            if !milestones.is_empty() {
-                release_percent = highest_met;
-            }
+        if event_info.sales_target_pending(env.ledger().timestamp()) {
+            return Err(TicketPaymentError::SalesTargetNotMet);
         }
 
-        let max_allowed = (total_revenue * release_percent as i128) / 10000;
-        let mut available_to_withdraw = max_allowed - balance.total_withdrawn;
+        let mut balance = get_event_balance(&env, event_id.clone(), token_address.clone());
+        let release_bps = organizer_release_bps(&env, &event_info);
 
-        if available_to_withdraw <= 0 {
-            return Ok(0);
+        let mut total_paid = 0;
+        for split in revenue_payees(&env, &event_info).iter() {
+            total_paid += pay_revenue_share(
+                &env,
+                &event_info,
+                &token_address,
+                &mut balance,
+                release_bps,
+                &split,
+            );
         }
 
-        if available_to_withdraw > balance.organizer_amount {
-            available_to_withdraw = balance.organizer_amount;
+        crate::storage::set_event_balance(&env, event_id, token_address, balance);
+        Ok(total_paid)
+    }
+
+    /// Pays `payee` their share of an event's released revenue in
+    /// `token_address`, returning the amount paid.
+    ///
+    /// `payee` must be listed in the event's revenue splits, or be the
+    /// organizer of an event without splits.
+    pub fn claim_revenue_share(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+        payee: Address,
+    ) -> Result<i128, TicketPaymentError> {
+        payee.require_auth();
+
+        let event_info = get_event_info(&env, &event_id)?;
+
+        // Escrow of a cancelled event is reserved for buyer refunds
+        if is_cancelled(&env, &event_info) {
+            return Err(TicketPaymentError::EventCancelled);
+        }
+        if event_info.sales_target_pending(env.ledger().timestamp()) {
+            return Err(TicketPaymentError::SalesTargetNotMet);
         }
 
-        token::Client::new(&env, &token_address).transfer(
-            &env.current_contract_address(),
-            &event_info.organizer_address,
-            &available_to_withdraw,
-        );
+        let split = revenue_payees(&env, &event_info)
+            .iter()
+            .find(|split| split.payee == payee)
+            .ok_or(TicketPaymentError::Unauthorized)?;
 
-        crate::storage::set_event_balance(
+        let mut balance = get_event_balance(&env, event_id.clone(), token_address.clone());
+        let release_bps = organizer_release_bps(&env, &event_info);
+        let paid = pay_revenue_share(
             &env,
-            event_id,
-            crate::types::EventBalance {
-                organizer_amount: balance.organizer_amount - available_to_withdraw,
-                total_withdrawn: balance.total_withdrawn + available_to_withdraw,
-                platform_fee: balance.platform_fee,
-            },
+            &event_info,
+            &token_address,
+            &mut balance,
+            release_bps,
+            &split,
         );
 
-        Ok(available_to_withdraw)
+        crate::storage::set_event_balance(&env, event_id, token_address, balance);
+        Ok(paid)
+    }
+
+    /// Returns how much of an event's revenue in `token_address` `payee` has
+    /// claimed so far.
+    pub fn get_revenue_claimed(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+        payee: Address,
+    ) -> Result<i128, TicketPaymentError> {
+        let event_info = get_event_info(&env, &event_id)?;
+        let balance = get_event_balance(&env, event_id, token_address.clone());
+        Ok(revenue_claimed(
+            &env,
+            &event_info,
+            &token_address,
+            &balance,
+            &payee,
+        ))
     }
 
-    /// Withdraw platform fees from escrow.
+    /// Withdraw platform fees held in `token_address` from escrow.
+    ///
+    /// Fees stay in escrow until the event settles, since a refund before then
+    /// hands the ticket's fee back to the buyer. A cancelled event releases
+    /// the fees left once every ticket has been refunded. Fees of payments
+    /// still pending stay in escrow until they are confirmed.
     pub fn withdraw_platform_fees(
         env: Env,
         event_id: String,
@@ -514,8 +749,19 @@ impl TicketPaymentContract {
         let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
         admin.require_auth();
 
-        let balance = get_event_balance(&env, event_id.clone());
-        if balance.platform_fee == 0 {
+        let event_registry_addr = get_event_registry(&env);
+        let registry_client = event_registry::Client::new(&env, &event_registry_addr);
+        let event_info = match registry_client.try_get_event(&event_id) {
+            Ok(Ok(Some(info))) => info,
+            _ => return Err(TicketPaymentError::EventNotFound),
+        };
+
+        check_escrow_released(&env, &event_info)?;
+
+        let balance = get_event_balance(&env, event_id.clone(), token_address.clone());
+        let pending = get_pending_escrow(&env, event_id.clone(), token_address.clone());
+        let platform_fee = balance.platform_fee - pending.platform_fee;
+        if platform_fee <= 0 {
             return Ok(0);
         }
 
@@ -523,20 +769,280 @@ impl TicketPaymentContract {
         token::Client::new(&env, &token_address).transfer(
             &env.current_contract_address(),
             &platform_wallet,
-            &balance.platform_fee,
+            &platform_fee,
         );
+        record_platform_fee_withdrawal(&env, &token_address, platform_fee);
 
         crate::storage::set_event_balance(
             &env,
             event_id,
+            token_address,
             crate::types::EventBalance {
                 organizer_amount: balance.organizer_amount,
                 total_withdrawn: balance.total_withdrawn,
-                platform_fee: 0,
+                platform_fee: pending.platform_fee,
+            },
+        );
+
+        Ok(platform_fee)
+    }
+
+    /// Sets the share of organizer revenue, in basis points, held in escrow
+    /// until `dispute_window` seconds after the event ends. Only callable by
+    /// the administrator.
+    pub fn set_settlement_config(
+        env: Env,
+        holdback_bps: u32,
+        dispute_window: u64,
+    ) -> Result<(), TicketPaymentError> {
+        let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        admin.require_auth();
+
+        if holdback_bps > 10000 {
+            return Err(TicketPaymentError::InvalidSettlementConfig);
+        }
+
+        set_settlement_config(
+            &env,
+            &SettlementConfig {
+                holdback_bps,
+                dispute_window,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_settlement_config(env: Env) -> SettlementConfig {
+        get_settlement_config(&env)
+    }
+
+    /// Pays out everything left in an event's escrow for `token_address`: the
+    /// organizer's share to the organizer and the fees to the platform wallet.
+    ///
+    /// Anyone can call this once the event has ended and its dispute window
+    /// has passed; an event without an end time ends when it is completed. A
+    /// cancelled event can be settled once every ticket has been refunded:
+    /// what is left, such as transfer fees, resale royalties and fees and the
+    /// parts of refunds its refund policy kept, was earned by the organizer
+    /// and the platform. Escrow of payments still pending is left for their
+    /// confirmation or expiry. Returns the amounts paid to the organizer and
+    /// the platform.
+    pub fn settle_event(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+    ) -> Result<(i128, i128), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+
+        let event_info = get_event_info(&env, &event_id)?;
+        check_escrow_released(&env, &event_info)?;
+
+        let mut balance = get_event_balance(&env, event_id.clone(), token_address.clone());
+        let pending = get_pending_escrow(&env, event_id.clone(), token_address.clone());
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+
+        // Every payee receives the rest of their share; rounding dust goes to
+        // the organizer
+        let mut organizer_amount = 0;
+        for split in revenue_payees(&env, &event_info).iter() {
+            organizer_amount += pay_revenue_share(
+                &env,
+                &event_info,
+                &token_address,
+                &mut balance,
+                10000,
+                &split,
+            );
+        }
+        let remaining = balance.organizer_amount - pending.organizer_amount;
+        if remaining > 0 {
+            token_client.transfer(&contract_address, &event_info.organizer_address, &remaining);
+            organizer_amount += remaining;
+            balance.total_withdrawn += remaining;
+            balance.organizer_amount -= remaining;
+        }
+
+        let platform_fee = core::cmp::max(balance.platform_fee - pending.platform_fee, 0);
+        if platform_fee > 0 {
+            token_client.transfer(&contract_address, get_platform_wallet(&env), &platform_fee);
+            record_platform_fee_withdrawal(&env, &token_address, platform_fee);
+            balance.platform_fee -= platform_fee;
+        }
+
+        crate::storage::set_event_balance(&env, event_id.clone(), token_address.clone(), balance);
+
+        env.events().publish(
+            (AgoraEvent::EventSettled,),
+            EventSettledEvent {
+                event_id,
+                token_address,
+                organizer_amount,
+                platform_fee,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok((organizer_amount, platform_fee))
+    }
+
+    /// Sweeps the platform fees held in `token_address` for up to `limit`
+    /// events, starting at index `start` of the events holding escrow in that
+    /// token, to the platform wallet. Only callable by the administrator.
+    ///
+    /// One call reads a single page. To sweep every event, start at 0 and call
+    /// again with the returned `next_index` until `complete` is set. Only
+    /// events whose escrow is released, as for `settle_event`, are swept; fees
+    /// of other events stay in escrow for their refunds.
+    pub fn withdraw_all_platform_fees(
+        env: Env,
+        token_address: Address,
+        start: u32,
+        limit: u32,
+    ) -> Result<PlatformFeeSweep, TicketPaymentError> {
+        let admin = get_admin(&env).ok_or(TicketPaymentError::NotInitialized)?;
+        admin.require_auth();
+
+        let events = get_escrow_events(&env, &token_address);
+        let next_index = core::cmp::min(start.saturating_add(limit), events.len());
+
+        let mut total = 0i128;
+        for index in start..next_index {
+            let event_id = events.get(index).unwrap();
+            let mut balance = get_event_balance(&env, event_id.clone(), token_address.clone());
+            let pending = get_pending_escrow(&env, event_id.clone(), token_address.clone());
+            let platform_fee = balance.platform_fee - pending.platform_fee;
+            if platform_fee <= 0 {
+                continue;
+            }
+            let event_info = get_event_info(&env, &event_id)?;
+            if check_escrow_released(&env, &event_info).is_err() {
+                continue;
+            }
+            total += platform_fee;
+            balance.platform_fee = pending.platform_fee;
+            crate::storage::set_event_balance(&env, event_id, token_address.clone(), balance);
+        }
+
+        if total > 0 {
+            token::Client::new(&env, &token_address).transfer(
+                &env.current_contract_address(),
+                get_platform_wallet(&env),
+                &total,
+            );
+            record_platform_fee_withdrawal(&env, &token_address, total);
+        }
+
+        env.events().publish(
+            (AgoraEvent::PlatformFeesSwept,),
+            PlatformFeesSweptEvent {
+                token_address,
+                amount: total,
+                timestamp: env.ledger().timestamp(),
             },
         );
 
-        Ok(balance.platform_fee)
+        Ok(PlatformFeeSweep {
+            amount: total,
+            next_index,
+            complete: next_index >= events.len(),
+        })
+    }
+
+    /// Returns the platform fees accrued, withdrawn and still pending in
+    /// `token_address` across all events.
+    pub fn get_platform_fee_ledger(env: Env, token_address: Address) -> PlatformFeeLedger {
+        get_platform_fee_ledger(&env, &token_address)
+    }
+
+    /// Compares the contract's balance of `token_address` with the escrow it
+    /// holds in that token for every event.
+    ///
+    /// The contract is solvent while its balance covers every organizer amount
+    /// and platform fee still held. Each call reads at most `limit` more
+    /// events, continuing where the previous call stopped; the cursor and the
+    /// liabilities summed so far are kept in storage. `solvent` is only set by
+    /// the call that reads the last event, after which the next audit starts
+    /// over from the first event.
+    pub fn audit_solvency(env: Env, token_address: Address, limit: u32) -> SolvencyReport {
+        let events = get_escrow_events(&env, &token_address);
+        let audit = get_solvency_audit(&env, &token_address);
+        let start = audit.next_index;
+        let next_index = core::cmp::min(start.saturating_add(limit), events.len());
+
+        let mut liabilities = audit.liabilities;
+        for index in start..next_index {
+            let balance =
+                get_event_balance(&env, events.get(index).unwrap(), token_address.clone());
+            liabilities += balance.organizer_amount + balance.platform_fee;
+        }
+
+        let balance =
+            token::Client::new(&env, &token_address).balance(&env.current_contract_address());
+        let complete = next_index >= events.len();
+        if complete {
+            remove_solvency_audit(&env, &token_address);
+        } else {
+            set_solvency_audit(
+                &env,
+                &token_address,
+                &SolvencyAudit {
+                    next_index,
+                    liabilities,
+                },
+            );
+        }
+        SolvencyReport {
+            liabilities,
+            balance,
+            next_index,
+            complete,
+            solvent: complete && balance >= liabilities,
+        }
+    }
+
+    /// Sums an event's ticket records in `token_address`, net of refunds, and
+    /// compares them with the escrow its sales and refunds moved.
+    ///
+    /// Reads at most `limit` payments starting at index `start`, adding them to
+    /// `carried`: the `tickets` of the previous page, or zero for the first.
+    /// Once the last page is read, `balanced` is true when every stroop
+    /// escrowed for the event's tickets is accounted for by a `Payment`.
+    pub fn reconcile_event(
+        env: Env,
+        event_id: String,
+        token_address: Address,
+        start: u32,
+        limit: u32,
+        carried: SalesEscrow,
+    ) -> Result<EscrowReconciliation, TicketPaymentError> {
+        let payment_ids = get_event_payments(&env, event_id.clone());
+        let next_index = core::cmp::min(start.saturating_add(limit), payment_ids.len());
+
+        let mut tickets = carried;
+        for index in start..next_index {
+            let Some(payment) = get_payment(&env, payment_ids.get(index).unwrap()) else {
+                continue;
+            };
+            if payment.token_address != token_address {
+                continue;
+            }
+            let refunded_organizer_amount = payment.refunded_amount - payment.refunded_fee;
+            tickets.organizer_amount += payment.organizer_amount - refunded_organizer_amount;
+            tickets.platform_fee += payment.platform_fee - payment.refunded_fee;
+        }
+
+        let escrow = get_sales_escrow(&env, event_id, token_address);
+        let complete = next_index >= payment_ids.len();
+        Ok(EscrowReconciliation {
+            balanced: complete && tickets == escrow,
+            tickets,
+            escrow,
+            next_index,
+            complete,
+        })
     }
 
     /// Returns all payments for a specific buyer.
@@ -582,51 +1088,315 @@ impl TicketPaymentContract {
             panic!("Contract not initialized");
         }
 
-        let mut payment =
+        let payment =
             get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        payment.buyer_address.require_auth();
 
-        if payment.status != PaymentStatus::Confirmed {
+        move_ticket(&env, payment_id, payment, to)
+    }
+
+    /// Returns the holder of a live ticket. Refunded and failed tickets no
+    /// longer have an owner.
+    pub fn owner_of(env: Env, payment_id: String) -> Result<Address, TicketPaymentError> {
+        let payment = get_payment(&env, payment_id).ok_or(TicketPaymentError::PaymentNotFound)?;
+        if !is_live_ticket(&payment) {
             return Err(TicketPaymentError::InvalidPaymentStatus);
         }
+        Ok(payment.buyer_address)
+    }
 
-        let from = payment.buyer_address.clone();
-        from.require_auth();
-
-        if from == to {
-            return Err(TicketPaymentError::InvalidAddress);
+    /// Returns how many live tickets `owner` holds.
+    pub fn balance(env: Env, owner: Address) -> u32 {
+        let mut count = 0;
+        for payment_id in crate::storage::get_buyer_payments(&env, owner).iter() {
+            if let Some(payment) = get_payment(&env, payment_id) {
+                if is_live_ticket(&payment) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Lets `approved` move one ticket on the holder's behalf until
+    /// `live_until_ledger`. An expiry before the current ledger revokes the
+    /// approval. Moving the ticket clears it.
+    pub fn approve(
+        env: Env,
+        approver: Address,
+        approved: Address,
+        payment_id: String,
+        live_until_ledger: u32,
+    ) -> Result<(), TicketPaymentError> {
+        approver.require_auth();
+
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        if payment.buyer_address != approver {
+            return Err(TicketPaymentError::Unauthorized);
+        }
+
+        if live_until_ledger < env.ledger().sequence() {
+            remove_approval(&env, payment_id.clone());
+        } else {
+            set_approval(
+                &env,
+                payment_id.clone(),
+                &TicketApproval {
+                    approved: approved.clone(),
+                    live_until_ledger,
+                },
+            );
+        }
+
+        env.events().publish(
+            (AgoraEvent::TicketApproved,),
+            TicketApprovedEvent {
+                payment_id,
+                owner: approver,
+                approved,
+                live_until_ledger,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the address currently approved to move a ticket, if any.
+    pub fn get_approved(env: Env, payment_id: String) -> Option<Address> {
+        get_approval(&env, payment_id).map(|approval| approval.approved)
+    }
+
+    /// Moves a ticket held by `from` to `to`, under the same rules and fee as
+    /// `transfer_ticket`.
+    pub fn transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        payment_id: String,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        from.require_auth();
+
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        if payment.buyer_address != from {
+            return Err(TicketPaymentError::Unauthorized);
+        }
+
+        move_ticket(&env, payment_id, payment, to)
+    }
+
+    /// Moves a ticket held by `from` to `to` on behalf of its approved
+    /// `spender`. The transfer fee is still charged to `from`.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        payment_id: String,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        spender.require_auth();
+
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        if payment.buyer_address != from {
+            return Err(TicketPaymentError::Unauthorized);
+        }
+        match get_approval(&env, payment_id.clone()) {
+            Some(approval) if approval.approved == spender => {}
+            _ => return Err(TicketPaymentError::Unauthorized),
+        }
+
+        move_ticket(&env, payment_id, payment, to)
+    }
+
+    /// Returns `ipfs://<metadata_cid>` for the ticket's event.
+    pub fn token_uri(env: Env, payment_id: String) -> Result<String, TicketPaymentError> {
+        let payment = get_payment(&env, payment_id).ok_or(TicketPaymentError::PaymentNotFound)?;
+        let event_info = get_event_info(&env, &payment.event_id)?;
+
+        let cid = event_info.metadata_cid;
+        let len = cid.len() as usize;
+        if len > MAX_METADATA_CID_LEN {
+            return Err(TicketPaymentError::InvalidTokenUri);
+        }
+
+        let mut buf = [0u8; IPFS_SCHEME.len() + MAX_METADATA_CID_LEN];
+        buf[..IPFS_SCHEME.len()].copy_from_slice(IPFS_SCHEME);
+        cid.copy_into_slice(&mut buf[IPFS_SCHEME.len()..IPFS_SCHEME.len() + len]);
+        Ok(String::from_bytes(&env, &buf[..IPFS_SCHEME.len() + len]))
+    }
+
+    /// Chooses who bears the event's platform fee on ticket sales. Only the
+    /// organizer can call this.
+    ///
+    /// Tickets already sold keep the split they were bought with, so refunds
+    /// return what each payer actually paid.
+    pub fn set_fee_mode(
+        env: Env,
+        event_id: String,
+        fee_mode: FeeMode,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+
+        let event_info = get_event_info(&env, &event_id)?;
+        event_info.organizer_address.require_auth();
+
+        set_fee_mode(&env, event_id, fee_mode);
+        Ok(())
+    }
+
+    pub fn get_fee_mode(env: Env, event_id: String) -> FeeMode {
+        get_fee_mode(&env, event_id)
+    }
+
+    /// Returns the `amount` `process_payment` expects for one ticket of a tier
+    /// right now, including the platform fee when buyers pay it.
+    pub fn get_ticket_price(
+        env: Env,
+        event_id: String,
+        ticket_tier_id: String,
+    ) -> Result<i128, TicketPaymentError> {
+        let event_info = get_event_info(&env, &event_id)?;
+        let tier = event_info
+            .tiers
+            .get(ticket_tier_id)
+            .ok_or(TicketPaymentError::TierNotFound)?;
+        let (_, active_price) = tier.active_price(env.ledger().timestamp());
+
+        checkout_price(
+            active_price,
+            event_info.platform_fee_percent,
+            get_fee_mode(&env, event_id),
+        )
+    }
+
+    /// Sets the resale markup cap and organizer royalty for an event, both in
+    /// basis points. Only the organizer can call this.
+    ///
+    /// The royalty plus the event's platform fee must leave the seller a
+    /// non-negative share of every resale.
+    pub fn set_resale_config(
+        env: Env,
+        event_id: String,
+        max_markup_bps: u32,
+        royalty_bps: u32,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
 
-        let transfer_fee = get_transfer_fee(&env, payment.event_id.clone());
+        let event_info = get_event_info(&env, &event_id)?;
+        event_info.organizer_address.require_auth();
+
+        if royalty_bps as u64 + event_info.platform_fee_percent as u64 > 10000 {
+            return Err(TicketPaymentError::InvalidResaleConfig);
+        }
 
-        if transfer_fee > 0 {
-            let token_address = crate::storage::get_usdc_token(&env);
-            let token_client = token::Client::new(&env, &token_address);
-            let contract_address = env.current_contract_address();
+        set_resale_config(
+            &env,
+            event_id,
+            &ResaleConfig {
+                max_markup_bps,
+                royalty_bps,
+            },
+        );
+        Ok(())
+    }
 
-            // Transfer fee from old owner to contract
-            token_client.transfer_from(&contract_address, &from, &contract_address, &transfer_fee);
+    pub fn get_resale_config(env: Env, event_id: String) -> ResaleConfig {
+        get_resale_config(&env, event_id)
+    }
 
-            // Update escrow balances (fee goes to organizer)
-            update_event_balance(&env, payment.event_id.clone(), transfer_fee, 0);
+    /// Offers a confirmed ticket for resale at `price`, in the token it was paid
+    /// with. Listing again replaces the previous price.
+    ///
+    /// The price may not exceed the ticket's face value plus the event's
+    /// `max_markup_bps`.
+    pub fn list_ticket(
+        env: Env,
+        payment_id: String,
+        price: i128,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
         }
 
-        // Update payment record
-        payment.buyer_address = to.clone();
-        let key = crate::types::DataKey::Payment(payment_id.clone());
-        env.storage().persistent().set(&key, &payment);
+        let payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+        let seller = payment.buyer_address.clone();
+        seller.require_auth();
+
+        if payment.status == PaymentStatus::CheckedIn {
+            return Err(TicketPaymentError::TicketAlreadyCheckedIn);
+        }
+        if payment.status != PaymentStatus::Confirmed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        if price <= 0 {
+            return Err(TicketPaymentError::InvalidPrice);
+        }
+
+        let event_info = get_event_info(&env, &payment.event_id)?;
+        if is_cancelled(&env, &event_info) {
+            return Err(TicketPaymentError::EventCancelled);
+        }
+
+        let config = get_resale_config(&env, payment.event_id.clone());
+        let max_price = payment
+            .amount
+            .checked_mul(10000 + config.max_markup_bps as i128)
+            .ok_or(TicketPaymentError::ArithmeticError)?
+            / 10000;
+        if price > max_price {
+            return Err(TicketPaymentError::ResalePriceTooHigh);
+        }
+
+        let listed_at = env.ledger().timestamp();
+        set_listing(
+            &env,
+            &Listing {
+                payment_id: payment_id.clone(),
+                seller: seller.clone(),
+                price,
+                listed_at,
+            },
+        );
+
+        env.events().publish(
+            (AgoraEvent::TicketListed,),
+            TicketListedEvent {
+                payment_id,
+                seller,
+                price,
+                timestamp: listed_at,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Withdraws a resale listing. Only the seller can call this.
+    pub fn cancel_listing(env: Env, payment_id: String) -> Result<(), TicketPaymentError> {
+        let listing =
+            get_listing(&env, payment_id.clone()).ok_or(TicketPaymentError::ListingNotFound)?;
+        listing.seller.require_auth();
 
-        // Update indices
-        remove_payment_from_buyer_index(&env, from.clone(), payment_id.clone());
-        add_payment_to_buyer_index(&env, to.clone(), payment_id.clone());
+        remove_listing(&env, payment_id.clone());
 
-        // Emit transfer event
         env.events().publish(
-            (AgoraEvent::TicketTransferred,),
-            TicketTransferredEvent {
+            (AgoraEvent::ListingCancelled,),
+            ListingCancelledEvent {
                 payment_id,
-                from,
-                to,
-                transfer_fee,
+                seller: listing.seller,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -634,11 +1404,185 @@ impl TicketPaymentContract {
         Ok(())
     }
 
+    pub fn get_listing(env: Env, payment_id: String) -> Option<Listing> {
+        get_listing(&env, payment_id)
+    }
+
+    /// Buys a listed ticket, moving it to `buyer` in the same call.
+    ///
+    /// `buyer` pays the listing price into the contract. The platform fee (the
+    /// event's `platform_fee_percent`) and the organizer royalty are kept in
+    /// escrow for the event, and the rest is paid to the seller.
+    ///
+    /// `expected_price` is the price the buyer agreed to; the purchase fails
+    /// with `InvalidPrice` if the seller re-listed the ticket at another price.
+    pub fn buy_resale(
+        env: Env,
+        payment_id: String,
+        buyer: Address,
+        expected_price: i128,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        buyer.require_auth();
+
+        let listing =
+            get_listing(&env, payment_id.clone()).ok_or(TicketPaymentError::ListingNotFound)?;
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+        // A listing only stands while its seller still holds the confirmed ticket
+        if payment.buyer_address != listing.seller {
+            return Err(TicketPaymentError::ListingNotFound);
+        }
+        if payment.status == PaymentStatus::CheckedIn {
+            return Err(TicketPaymentError::TicketAlreadyCheckedIn);
+        }
+        if payment.status != PaymentStatus::Confirmed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+        if buyer == listing.seller {
+            return Err(TicketPaymentError::InvalidAddress);
+        }
+        if listing.price != expected_price {
+            return Err(TicketPaymentError::InvalidPrice);
+        }
+
+        let event_info = get_event_info(&env, &payment.event_id)?;
+        if is_cancelled(&env, &event_info) {
+            return Err(TicketPaymentError::EventCancelled);
+        }
+
+        let config = get_resale_config(&env, payment.event_id.clone());
+        let price = listing.price;
+        let platform_fee = price
+            .checked_mul(event_info.platform_fee_percent as i128)
+            .ok_or(TicketPaymentError::ArithmeticError)?
+            / 10000;
+        let royalty = price
+            .checked_mul(config.royalty_bps as i128)
+            .ok_or(TicketPaymentError::ArithmeticError)?
+            / 10000;
+        let seller_amount = price - platform_fee - royalty;
+        if seller_amount < 0 {
+            return Err(TicketPaymentError::ArithmeticError);
+        }
+
+        let token_client = token::Client::new(&env, &payment.token_address);
+        let contract_address = env.current_contract_address();
+
+        if token_client.allowance(&buyer, &contract_address) < price {
+            return Err(TicketPaymentError::InsufficientAllowance);
+        }
+        token_client.transfer_from(&contract_address, &buyer, &contract_address, &price);
+        if seller_amount > 0 {
+            token_client.transfer(&contract_address, &listing.seller, &seller_amount);
+        }
+
+        // The royalty joins the organizer's escrow share, the fee the platform's
+        update_event_balance(
+            &env,
+            payment.event_id.clone(),
+            payment.token_address.clone(),
+            royalty,
+            platform_fee,
+        );
+
+        // The resale buyer paid for the ticket, so refunds now go to them
+        payment.buyer_address = buyer.clone();
+        payment.payer_address = buyer.clone();
+        store_payment(&env, payment);
+        remove_listing(&env, payment_id.clone());
+        remove_approval(&env, payment_id.clone());
+
+        remove_payment_from_buyer_index(&env, listing.seller.clone(), payment_id.clone());
+        add_payment_to_buyer_index(&env, buyer.clone(), payment_id.clone());
+
+        env.events().publish(
+            (AgoraEvent::TicketResold,),
+            TicketResoldEvent {
+                payment_id,
+                seller: listing.seller,
+                buyer,
+                price,
+                royalty,
+                platform_fee,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Redeems a confirmed ticket at the door.
+    ///
+    /// `scanner` must be the event organizer or hold the event's `Scanner` role
+    /// in the registry; `gate_id` identifies where the ticket was scanned. A
+    /// checked-in ticket can no longer be transferred, nor refunded unless the
+    /// event is cancelled later, and scanning it again fails. Tickets of a cancelled event cannot be checked in, so
+    /// their buyers keep the right to a full refund.
+    pub fn check_in(
+        env: Env,
+        payment_id: String,
+        scanner: Address,
+        gate_id: String,
+    ) -> Result<(), TicketPaymentError> {
+        if !is_initialized(&env) {
+            panic!("Contract not initialized");
+        }
+        scanner.require_auth();
+
+        let mut payment =
+            get_payment(&env, payment_id.clone()).ok_or(TicketPaymentError::PaymentNotFound)?;
+
+        let event_info = get_event_info(&env, &payment.event_id)?;
+        require_event_role(
+            &env,
+            &event_info,
+            event_registry::EventRole::Scanner,
+            &scanner,
+        )?;
+
+        if is_cancelled(&env, &event_info) {
+            return Err(TicketPaymentError::EventCancelled);
+        }
+        if payment.status == PaymentStatus::CheckedIn {
+            return Err(TicketPaymentError::TicketAlreadyCheckedIn);
+        }
+        if payment.status != PaymentStatus::Confirmed {
+            return Err(TicketPaymentError::InvalidPaymentStatus);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        payment.status = PaymentStatus::CheckedIn;
+        payment.checked_in_at = Some(timestamp);
+        store_payment(&env, payment.clone());
+
+        env.events().publish(
+            (AgoraEvent::TicketCheckedIn,),
+            TicketCheckedInEvent {
+                payment_id,
+                event_id: payment.event_id,
+                holder: payment.buyer_address,
+                scanner,
+                gate_id,
+                timestamp,
+            },
+        );
+
+        Ok(())
+    }
+
     /// Triggers a bulk refund for a cancelled event. Processes in batches.
+    ///
+    /// `caller` must be the event organizer or hold the event's `Finance` role
+    /// in the registry.
     pub fn trigger_bulk_refund(
         env: Env,
         event_id: String,
         batch_size: u32,
+        caller: Address,
     ) -> Result<u32, TicketPaymentError> {
         if !is_initialized(&env) {
             panic!("Contract not initialized");
@@ -652,14 +1596,17 @@ impl TicketPaymentContract {
             _ => return Err(TicketPaymentError::EventNotFound),
         };
 
-        event_info.organizer_address.require_auth();
-
-        // In a bulk refund, we assume the event is cancelled or inactive
-        if event_info.is_active {
-            // Technically organizers might want to refund even if active,
-            // but for mass cancellations it's safer to check it's inactive or cancelled.
-            // Requirement says "event cannot proceed", implying cancellation.
-            // We'll allow it anyway as long as they are the organizer.
+        caller.require_auth();
+        require_event_role(
+            &env,
+            &event_info,
+            event_registry::EventRole::Finance,
+            &caller,
+        )?;
+
+        // Bulk refunds are only for events that cannot proceed
+        if !is_cancelled(&env, &event_info) {
+            return Err(TicketPaymentError::EventNotCancelled);
         }
 
         let start_index = get_bulk_refund_index(&env, event_id.clone());
@@ -670,36 +1617,42 @@ impl TicketPaymentContract {
             return Ok(0);
         }
 
-        let end_index = core::cmp::min(start_index + batch_size, total_payments);
+        let end_index = core::cmp::min(start_index.saturating_add(batch_size), total_payments);
         let mut processed_count = 0;
+        let mut skipped_count = 0;
         let mut total_refunded = 0;
 
-        let token_address = crate::storage::get_usdc_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
         let contract_address = env.current_contract_address();
 
         for i in start_index..end_index {
             let payment_id = payment_ids.get(i).unwrap();
             if let Some(mut payment) = get_payment(&env, payment_id.clone()) {
-                if payment.status == PaymentStatus::Confirmed {
-                    // Refund full amount to buyer
-                    token_client.transfer(
+                // Tickets checked in before the cancellation are refunded too
+                if payment.status == PaymentStatus::Confirmed
+                    || payment.status == PaymentStatus::CheckedIn
+                {
+                    // Since it's a full refund, both the organizer amount and
+                    // the platform fee are removed from escrow. A ticket the
+                    // escrow cannot cover is skipped so the batch still advances.
+                    let Ok(refunded_fee) = debit_escrow(&env, &payment, payment.amount, 0) else {
+                        skipped_count += 1;
+                        continue;
+                    };
+
+                    // Refund full amount to the payer, in the token they paid with
+                    token::Client::new(&env, &payment.token_address).transfer(
                         &contract_address,
-                        &payment.buyer_address,
+                        &payment.payer_address,
                         &payment.amount,
                     );
 
                     // Update payment status
                     payment.status = PaymentStatus::Refunded;
+                    payment.refunded_amount = payment.amount;
+                    payment.refunded_fee = refunded_fee;
                     payment.confirmed_at = Some(env.ledger().timestamp());
                     store_payment(&env, payment.clone());
-
-                    // Update event balance (decrement organizer amount and platform fee)
-                    // Since it's a full refund, both parts are removed from escrow
-                    let mut balance = get_event_balance(&env, event_id.clone());
-                    balance.organizer_amount -= payment.organizer_amount;
-                    balance.platform_fee -= payment.platform_fee;
-                    crate::storage::set_event_balance(&env, event_id.clone(), balance);
+                    remove_live_ticket(&env, event_id.clone());
 
                     total_refunded += payment.amount;
                     processed_count += 1;
@@ -715,6 +1668,7 @@ impl TicketPaymentContract {
             BulkRefundProcessedEvent {
                 event_id,
                 refund_count: processed_count,
+                skipped_count,
                 total_refunded,
                 timestamp: env.ledger().timestamp(),
             },
@@ -724,9 +1678,485 @@ impl TicketPaymentContract {
     }
 }
 
+fn get_event_info(
+    env: &Env,
+    event_id: &String,
+) -> Result<event_registry::EventInfo, TicketPaymentError> {
+    let registry_client = event_registry::Client::new(env, &get_event_registry(env));
+    match registry_client.try_get_event(event_id) {
+        Ok(Ok(Some(info))) => Ok(info),
+        _ => Err(TicketPaymentError::EventNotFound),
+    }
+}
+
+/// Allows `caller` if it is the event organizer or the registry records it as
+/// holding `role` on the event.
+fn require_event_role(
+    env: &Env,
+    event_info: &event_registry::EventInfo,
+    role: event_registry::EventRole,
+    caller: &Address,
+) -> Result<(), TicketPaymentError> {
+    if caller == &event_info.organizer_address {
+        return Ok(());
+    }
+    let registry_client = event_registry::Client::new(env, &get_event_registry(env));
+    if registry_client.has_role(&event_info.event_id, &role, caller) {
+        Ok(())
+    } else {
+        Err(TicketPaymentError::Unauthorized)
+    }
+}
+
+/// Returns true if an event was cancelled or missed its sales target, which
+/// cancels it without any action from the organizer.
+fn is_cancelled(env: &Env, event_info: &event_registry::EventInfo) -> bool {
+    event_info.status == event_registry::EventStatus::Cancelled
+        || event_info.sales_target_missed(env.ledger().timestamp())
+}
+
+/// Returns true once an event has ended and its dispute window has passed.
+/// An event without an end time ends when the organizer marks it completed.
+fn is_settled(env: &Env, event_info: &event_registry::EventInfo) -> bool {
+    let ended_at = if event_info.end_time > 0 {
+        event_info.end_time
+    } else if event_info.status == event_registry::EventStatus::Completed {
+        event_info.completed_at
+    } else {
+        return false;
+    };
+    let config = get_settlement_config(env);
+    env.ledger().timestamp() >= ended_at.saturating_add(config.dispute_window)
+}
+
+/// Checks that everything left in an event's escrow can be paid out: the event
+/// has settled, or it was cancelled and has refunded every ticket, so nothing
+/// it holds is owed to buyers any more.
+fn check_escrow_released(
+    env: &Env,
+    event_info: &event_registry::EventInfo,
+) -> Result<(), TicketPaymentError> {
+    if is_cancelled(env, event_info) {
+        // Escrow of a cancelled event is reserved for buyer refunds until then
+        if get_live_tickets(env, event_info.event_id.clone()) > 0 {
+            return Err(TicketPaymentError::EventCancelled);
+        }
+        return Ok(());
+    }
+    if event_info.sales_target_pending(env.ledger().timestamp()) {
+        return Err(TicketPaymentError::SalesTargetNotMet);
+    }
+    if !is_settled(env, event_info) {
+        return Err(TicketPaymentError::SettlementNotReady);
+    }
+    Ok(())
+}
+
+/// Returns the share of organizer revenue, in basis points, that can be
+/// withdrawn now.
+///
+/// Before settlement the highest milestone reached caps the share, and the
+/// settlement holdback is kept back on top of that. A settled event releases
+/// everything.
+fn organizer_release_bps(env: &Env, event_info: &event_registry::EventInfo) -> u32 {
+    if is_settled(env, event_info) {
+        return 10000;
+    }
+
+    let mut release_percent = 10000u32;
+    if let Some(milestones) = &event_info.milestone_plan {
+        let mut highest_met = 0u32;
+        for milestone in milestones.iter() {
+            if event_info.current_supply >= milestone.sales_threshold
+                && milestone.release_percent > highest_met
+            {
+                highest_met = milestone.release_percent;
+            }
+        }
+        if !milestones.is_empty() {
+            release_percent = highest_met;
+        }
+    }
+
+    let holdback_bps = get_settlement_config(env).holdback_bps;
+    core::cmp::min(release_percent, 10000 - holdback_bps)
+}
+
+/// Returns the payees sharing an event's organizer revenue. Without splits the
+/// organizer receives everything.
+fn revenue_payees(
+    env: &Env,
+    event_info: &event_registry::EventInfo,
+) -> Vec<event_registry::RevenueSplit> {
+    match &event_info.revenue_splits {
+        Some(splits) => splits.clone(),
+        None => soroban_sdk::vec![
+            env,
+            event_registry::RevenueSplit {
+                payee: event_info.organizer_address.clone(),
+                bps: 10000,
+            }
+        ],
+    }
+}
+
+/// Returns how much of an event's revenue in `token` a payee has been paid.
+/// Without splits the organizer is the only payee, so every withdrawal is theirs.
+fn revenue_claimed(
+    env: &Env,
+    event_info: &event_registry::EventInfo,
+    token: &Address,
+    balance: &EventBalance,
+    payee: &Address,
+) -> i128 {
+    if event_info.revenue_splits.is_none() {
+        return balance.total_withdrawn;
+    }
+    get_revenue_claimed(
+        env,
+        event_info.event_id.clone(),
+        token.clone(),
+        payee.clone(),
+    )
+}
+
+/// Pays a payee the part of their share that is released but not yet claimed,
+/// updating `balance` in place. Returns the amount paid.
+///
+/// A payee's share is `split.bps` of the released revenue, where the released
+/// revenue is `release_bps` of everything the organizer side has earned.
+/// Revenue of payments still pending is not earned yet.
+fn pay_revenue_share(
+    env: &Env,
+    event_info: &event_registry::EventInfo,
+    token: &Address,
+    balance: &mut EventBalance,
+    release_bps: u32,
+    split: &event_registry::RevenueSplit,
+) -> i128 {
+    let pending = get_pending_escrow(env, event_info.event_id.clone(), token.clone());
+    let available = balance.organizer_amount - pending.organizer_amount;
+    let total_revenue = available + balance.total_withdrawn;
+    let released = total_revenue * release_bps as i128 / 10000;
+    let entitled = released * split.bps as i128 / 10000;
+    let claimed = revenue_claimed(env, event_info, token, balance, &split.payee);
+
+    let amount = core::cmp::min(entitled - claimed, available);
+    if amount <= 0 {
+        return 0;
+    }
+
+    token::Client::new(env, token).transfer(&env.current_contract_address(), &split.payee, &amount);
+    if event_info.revenue_splits.is_some() {
+        set_revenue_claimed(
+            env,
+            event_info.event_id.clone(),
+            token.clone(),
+            split.payee.clone(),
+            claimed + amount,
+        );
+    }
+    balance.organizer_amount -= amount;
+    balance.total_withdrawn += amount;
+    amount
+}
+
+/// Returns the confirmation window for an event: its own, else the contract
+/// default. 0 means pending payments never expire.
+fn confirmation_window(env: &Env, event_id: String) -> u64 {
+    get_event_confirmation_window(env, event_id)
+        .unwrap_or_else(|| get_default_confirmation_window(env))
+}
+
+/// Returns true once a payment has outlived its event's confirmation window.
+fn is_expired(env: &Env, payment: &Payment) -> bool {
+    let window = confirmation_window(env, payment.event_id.clone());
+    window > 0 && env.ledger().timestamp() >= payment.created_at.saturating_add(window)
+}
+
+/// Takes `refund_amount` of a payment out of its event's escrow, returning the
+/// platform fee given back with it.
+///
+/// The platform returns its fee on the refunded part, counted before the
+/// refund policy's `processing_fee` comes off, so the organizer keeps the whole
+/// processing fee. Whatever is not refunded stays in escrow. If the organizer
+/// (or platform) has already withdrawn this share, the refund cannot be
+/// covered.
+fn debit_escrow(
+    env: &Env,
+    payment: &Payment,
+    refund_amount: i128,
+    processing_fee: i128,
+) -> Result<i128, TicketPaymentError> {
+    let refunded_fee = payment
+        .platform_fee
+        .checked_mul(refund_amount + processing_fee)
+        .ok_or(TicketPaymentError::ArithmeticError)?
+        / payment.amount;
+    let refunded_organizer_amount = refund_amount - refunded_fee;
+
+    let mut balance =
+        get_event_balance(env, payment.event_id.clone(), payment.token_address.clone());
+    if balance.organizer_amount < refunded_organizer_amount || balance.platform_fee < refunded_fee {
+        return Err(TicketPaymentError::InsufficientEscrow);
+    }
+    balance.organizer_amount -= refunded_organizer_amount;
+    balance.platform_fee -= refunded_fee;
+    crate::storage::set_event_balance(
+        env,
+        payment.event_id.clone(),
+        payment.token_address.clone(),
+        balance,
+    );
+    record_platform_fee(env, &payment.token_address, -refunded_fee);
+    record_sales_escrow(
+        env,
+        payment.event_id.clone(),
+        payment.token_address.clone(),
+        -refunded_organizer_amount,
+        -refunded_fee,
+    );
+    // A refunded or expired payment is no longer pending
+    if payment.status == PaymentStatus::Pending {
+        record_pending_escrow(
+            env,
+            payment.event_id.clone(),
+            payment.token_address.clone(),
+            -payment.organizer_amount,
+            -payment.platform_fee,
+        );
+    }
+    Ok(refunded_fee)
+}
+
+/// Marks a stale pending payment Failed, returning its full amount to the
+/// payer and its ticket to the registry's inventory.
+#[allow(deprecated)]
+fn expire_pending_payment(
+    env: &Env,
+    payment_id: String,
+    mut payment: Payment,
+) -> Result<(), TicketPaymentError> {
+    let refunded_fee = debit_escrow(env, &payment, payment.amount, 0)?;
+
+    let registry_client = event_registry::Client::new(env, &get_event_registry(env));
+    registry_client.decrement_inventory(&payment.event_id, &payment.ticket_tier_id);
+    remove_live_ticket(env, payment.event_id.clone());
+
+    token::Client::new(env, &payment.token_address).transfer(
+        &env.current_contract_address(),
+        &payment.payer_address,
+        &payment.amount,
+    );
+
+    payment.status = PaymentStatus::Failed;
+    payment.refunded_amount = payment.amount;
+    payment.refunded_fee = refunded_fee;
+    store_payment(env, payment);
+
+    env.events().publish(
+        (AgoraEvent::PaymentStatusChanged,),
+        PaymentStatusChangedEvent {
+            payment_id,
+            old_status: PaymentStatus::Pending,
+            new_status: PaymentStatus::Failed,
+            transaction_hash: String::from_str(env, "expired"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Tickets that still have a holder: refunded and failed tickets are burned.
+fn is_live_ticket(payment: &Payment) -> bool {
+    matches!(
+        payment.status,
+        PaymentStatus::Pending | PaymentStatus::Confirmed | PaymentStatus::CheckedIn
+    )
+}
+
+/// Moves a confirmed ticket to `to`, charging the event's transfer fee to the
+/// current holder. Callers are responsible for authorizing the move.
+#[allow(deprecated)]
+fn move_ticket(
+    env: &Env,
+    payment_id: String,
+    mut payment: Payment,
+    to: Address,
+) -> Result<(), TicketPaymentError> {
+    if payment.status == PaymentStatus::CheckedIn {
+        return Err(TicketPaymentError::TicketAlreadyCheckedIn);
+    }
+    if payment.status != PaymentStatus::Confirmed {
+        return Err(TicketPaymentError::InvalidPaymentStatus);
+    }
+
+    let from = payment.buyer_address.clone();
+    if from == to {
+        return Err(TicketPaymentError::InvalidAddress);
+    }
+
+    let transfer_fee = get_transfer_fee(env, payment.event_id.clone());
+
+    // The fee is charged in the token the ticket was paid with
+    if transfer_fee > 0 {
+        let token_client = token::Client::new(env, &payment.token_address);
+        let contract_address = env.current_contract_address();
+
+        // Transfer fee from old owner to contract
+        token_client.transfer_from(&contract_address, &from, &contract_address, &transfer_fee);
+
+        // Update escrow balances (fee goes to organizer)
+        update_event_balance(
+            env,
+            payment.event_id.clone(),
+            payment.token_address.clone(),
+            transfer_fee,
+            0,
+        );
+    }
+
+    // Update payment record; refunds still go to whoever paid for the ticket
+    payment.buyer_address = to.clone();
+    store_payment(env, payment);
+    remove_listing(env, payment_id.clone());
+    remove_approval(env, payment_id.clone());
+
+    // Update indices
+    remove_payment_from_buyer_index(env, from.clone(), payment_id.clone());
+    add_payment_to_buyer_index(env, to.clone(), payment_id.clone());
+
+    // Emit transfer event
+    env.events().publish(
+        (AgoraEvent::TicketTransferred,),
+        TicketTransferredEvent {
+            payment_id,
+            from,
+            to,
+            transfer_fee,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
 fn validate_address(env: &Env, address: &Address) -> Result<(), TicketPaymentError> {
     if address == &env.current_contract_address() {
         return Err(TicketPaymentError::InvalidAddress);
     }
     Ok(())
 }
+
+/// Applies the tier's refund policy to `payment` at `now`. An unscheduled event
+/// (`start_time == 0`) counts as far away, so the earliest window applies.
+fn policy_refund_amount(
+    payment: &Payment,
+    tier: &event_registry::TicketTier,
+    start_time: u64,
+    now: u64,
+) -> Result<i128, TicketPaymentError> {
+    let policy = &tier.refund_policy;
+    let seconds_before_start = if start_time == 0 {
+        u64::MAX
+    } else {
+        start_time.saturating_sub(now)
+    };
+    let refund_bps = policy.refund_bps(seconds_before_start);
+    let refund = payment
+        .amount
+        .checked_mul(refund_bps as i128)
+        .ok_or(TicketPaymentError::ArithmeticError)?
+        / 10000
+        - policy.processing_fee;
+
+    if refund <= 0 {
+        return Err(TicketPaymentError::TicketNotRefundable);
+    }
+    Ok(refund)
+}
+
+/// Returns what a payer is charged for one ticket priced at `price`.
+fn checkout_price(
+    price: i128,
+    fee_bps: u32,
+    fee_mode: FeeMode,
+) -> Result<i128, TicketPaymentError> {
+    match fee_mode {
+        FeeMode::OrganizerAbsorbs => Ok(price),
+        FeeMode::BuyerPays => price
+            .checked_mul(fee_bps as i128)
+            .map(|fee| fee / 10000)
+            .and_then(|fee| price.checked_add(fee))
+            .ok_or(TicketPaymentError::ArithmeticError),
+    }
+}
+
+const IPFS_SCHEME: &[u8] = b"ipfs://";
+
+/// Longest metadata CID `token_uri` can prefix with `ipfs://`.
+const MAX_METADATA_CID_LEN: usize = 128;
+
+/// Longest `payment_id` accepted for a batch, leaving room for the `-<index>` suffix.
+const MAX_ORDER_ID_LEN: usize = 64;
+
+/// Returns the ticket IDs for order `order_id`: the order ID itself for a single
+/// ticket, `<order_id>-<index>` for each ticket of a batch. Fails if the order or
+/// any derived ticket ID is already taken, so stored records are never overwritten.
+fn derive_ticket_ids(
+    env: &Env,
+    order_id: &String,
+    quantity: u32,
+) -> Result<Vec<String>, TicketPaymentError> {
+    if order_id.is_empty() {
+        return Err(TicketPaymentError::InvalidPaymentId);
+    }
+    if get_order(env, order_id.clone()).is_some() {
+        return Err(TicketPaymentError::PaymentAlreadyExists);
+    }
+
+    let mut ticket_ids = Vec::new(env);
+    for index in 0..quantity {
+        let ticket_id = if quantity == 1 {
+            order_id.clone()
+        } else {
+            batch_ticket_id(env, order_id, index)?
+        };
+        if has_payment(env, ticket_id.clone()) {
+            return Err(TicketPaymentError::PaymentAlreadyExists);
+        }
+        ticket_ids.push_back(ticket_id);
+    }
+    Ok(ticket_ids)
+}
+
+/// Formats `<order_id>-<index>` without allocating.
+fn batch_ticket_id(env: &Env, order_id: &String, index: u32) -> Result<String, TicketPaymentError> {
+    let len = order_id.len() as usize;
+    if len > MAX_ORDER_ID_LEN {
+        return Err(TicketPaymentError::InvalidPaymentId);
+    }
+
+    // u32::MAX has 10 decimal digits
+    let mut buf = [0u8; MAX_ORDER_ID_LEN + 11];
+    order_id.copy_into_slice(&mut buf[..len]);
+    buf[len] = b'-';
+
+    let mut digits = [0u8; 10];
+    let mut count = 0;
+    let mut rest = index;
+    loop {
+        digits[count] = b'0' + (rest % 10) as u8;
+        count += 1;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    for (offset, digit) in digits[..count].iter().rev().enumerate() {
+        buf[len + 1 + offset] = *digit;
+    }
+
+    Ok(String::from_bytes(env, &buf[..len + 1 + count]))
+}
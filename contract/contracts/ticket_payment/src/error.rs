@@ -19,6 +19,22 @@ pub enum TicketPaymentError {
     TransferVerificationFailed = 13,
     ArithmeticError = 14,
     InvalidPrice = 15,
+    EventCancelled = 16,
+    EventNotCancelled = 17,
+    PaymentAlreadyExists = 18,
+    InvalidPaymentId = 19,
+    Unauthorized = 20,
+    InsufficientEscrow = 21,
+    TicketAlreadyCheckedIn = 22,
+    ListingNotFound = 23,
+    ResalePriceTooHigh = 24,
+    InvalidResaleConfig = 25,
+    InvalidTokenUri = 26,
+    PaymentExpired = 27,
+    PaymentNotExpired = 28,
+    SettlementNotReady = 29,
+    InvalidSettlementConfig = 30,
+    SalesTargetNotMet = 31,
 }
 
 impl core::fmt::Display for TicketPaymentError {
@@ -54,6 +70,53 @@ impl core::fmt::Display for TicketPaymentError {
                     "Paid amount does not match the active price for this tier"
                 )
             }
+            TicketPaymentError::EventCancelled => {
+                write!(f, "Event is cancelled; escrow is reserved for refunds")
+            }
+            TicketPaymentError::EventNotCancelled => write!(f, "Event is not cancelled"),
+            TicketPaymentError::PaymentAlreadyExists => {
+                write!(f, "A payment or ticket with this ID already exists")
+            }
+            TicketPaymentError::InvalidPaymentId => {
+                write!(f, "Payment ID is empty or too long to derive ticket IDs")
+            }
+            TicketPaymentError::Unauthorized => write!(f, "Caller not authorized for action"),
+            TicketPaymentError::InsufficientEscrow => {
+                write!(f, "Escrow no longer holds enough funds to cover the refund")
+            }
+            TicketPaymentError::TicketAlreadyCheckedIn => {
+                write!(f, "Ticket has already been checked in")
+            }
+            TicketPaymentError::ListingNotFound => {
+                write!(f, "Ticket is not listed for resale by its holder")
+            }
+            TicketPaymentError::ResalePriceTooHigh => {
+                write!(f, "Resale price exceeds the organizer's markup cap")
+            }
+            TicketPaymentError::InvalidResaleConfig => {
+                write!(
+                    f,
+                    "Royalty and platform fee must not exceed 100% of a resale"
+                )
+            }
+            TicketPaymentError::InvalidTokenUri => {
+                write!(f, "Event metadata CID is too long to build a token URI")
+            }
+            TicketPaymentError::PaymentExpired => {
+                write!(f, "Payment confirmation deadline has passed")
+            }
+            TicketPaymentError::PaymentNotExpired => {
+                write!(f, "Payment is still within its confirmation deadline")
+            }
+            TicketPaymentError::SettlementNotReady => {
+                write!(f, "Event has not ended or its dispute window is still open")
+            }
+            TicketPaymentError::InvalidSettlementConfig => {
+                write!(f, "Settlement holdback must be between 0 and 10000")
+            }
+            TicketPaymentError::SalesTargetNotMet => {
+                write!(f, "Event has not reached its minimum sales target")
+            }
         }
     }
 }
@@ -11,6 +11,15 @@ pub enum AgoraEvent {
     TicketTransferred,
     PriceSwitched,
     BulkRefundProcessed,
+    ConfirmerAdded,
+    ConfirmerRemoved,
+    TicketCheckedIn,
+    TicketListed,
+    ListingCancelled,
+    TicketResold,
+    TicketApproved,
+    EventSettled,
+    PlatformFeesSwept,
 }
 
 #[contracttype]
@@ -18,9 +27,11 @@ pub enum AgoraEvent {
 pub struct PaymentProcessedEvent {
     pub payment_id: String,
     pub event_id: String,
+    pub payer_address: Address,
     pub buyer_address: Address,
-    pub amount: i128,
+    pub amount: i128, // total charged to the payer, any fee passed to them included
     pub platform_fee: i128,
+    pub organizer_amount: i128,
     pub timestamp: u64,
 }
 
@@ -63,6 +74,7 @@ pub struct TicketTransferredEvent {
 pub struct PriceSwitchedEvent {
     pub event_id: String,
     pub tier_id: String,
+    pub phase_index: u32,
     pub new_price: i128,
     pub timestamp: u64,
 }
@@ -72,6 +84,82 @@ pub struct PriceSwitchedEvent {
 pub struct BulkRefundProcessedEvent {
     pub event_id: String,
     pub refund_count: u32,
+    /// Tickets in the batch whose refund escrow could not cover
+    pub skipped_count: u32,
     pub total_refunded: i128,
     pub timestamp: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfirmerUpdatedEvent {
+    pub confirmer: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketCheckedInEvent {
+    pub payment_id: String,
+    pub event_id: String,
+    pub holder: Address,
+    pub scanner: Address,
+    pub gate_id: String,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketListedEvent {
+    pub payment_id: String,
+    pub seller: Address,
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingCancelledEvent {
+    pub payment_id: String,
+    pub seller: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketResoldEvent {
+    pub payment_id: String,
+    pub seller: Address,
+    pub buyer: Address,
+    pub price: i128,
+    pub royalty: i128,
+    pub platform_fee: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketApprovedEvent {
+    pub payment_id: String,
+    pub owner: Address,
+    pub approved: Address,
+    pub live_until_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventSettledEvent {
+    pub event_id: String,
+    pub token_address: Address,
+    pub organizer_amount: i128,
+    pub platform_fee: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeesSweptEvent {
+    pub token_address: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
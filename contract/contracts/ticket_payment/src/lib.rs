@@ -1,9 +1,12 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 pub mod contract;
 pub mod error;
 pub mod events;
 pub mod storage;
 pub mod types;
 
+#[cfg(test)]
+mod prop_test;
 #[cfg(test)]
 mod test;
@@ -0,0 +1,375 @@
+//! Stateful property tests.
+//!
+//! Each run drives the payment contract and a real registry through a random
+//! sequence of operations built from a seed, checking the escrow and ticket
+//! invariants after every step. A failing run reports its seed, step and
+//! operation so it can be replayed.
+
+use super::contract::{event_registry, TicketPaymentContract, TicketPaymentContractClient};
+use super::types::{FeeMode, PaymentStatus, SalesEscrow};
+use agora_types::testutils::{id, Rng};
+use soroban_sdk::{testutils::Address as _, token, Address, Env, Map, String, Vec};
+
+const BUYERS: u32 = 4;
+const FUNDS: i128 = 100000_0000000;
+
+/// A contract call; purchases are drawn most often so every other call has
+/// tickets to act on.
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Register,
+    Pay,
+    Confirm,
+    Refund,
+    Transfer,
+    Resell,
+    CheckIn,
+    Withdraw,
+    SweepFees,
+    SetStatus,
+    BulkRefund,
+}
+
+impl Op {
+    fn generate(rng: &mut Rng) -> Op {
+        match rng.below(16) {
+            0 => Op::Register,
+            1..=4 => Op::Pay,
+            5 | 6 => Op::Confirm,
+            7 | 8 => Op::Refund,
+            9 => Op::Transfer,
+            10 => Op::Resell,
+            11 => Op::CheckIn,
+            12 => Op::Withdraw,
+            13 => Op::SweepFees,
+            14 => Op::SetStatus,
+            _ => Op::BulkRefund,
+        }
+    }
+}
+
+struct Harness<'a> {
+    env: &'a Env,
+    client: TicketPaymentContractClient<'a>,
+    registry: ::event_registry::EventRegistryClient<'a>,
+    usdc_id: Address,
+    organizer: Address,
+    confirmer: Address,
+    rng: Rng,
+    /// (seed, step, op) being run, reported by failed assertions
+    at: (u64, u32, Op),
+    buyers: Vec<Address>,
+    events: Vec<String>,
+    tickets: Vec<String>,
+    orders: u32,
+}
+
+impl<'a> Harness<'a> {
+    fn new(env: &'a Env, seed: u64) -> Self {
+        env.mock_all_auths();
+        let contract_id = env.register(TicketPaymentContract, ());
+        let client = TicketPaymentContractClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        let usdc_id = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+
+        let registry_id = env.register(::event_registry::EventRegistry, ());
+        let registry = ::event_registry::EventRegistryClient::new(env, &registry_id);
+        registry.initialize(&admin, &Address::generate(env), &500);
+        registry.set_ticket_payment_contract(&contract_id);
+        client.initialize(&admin, &usdc_id, &Address::generate(env), &registry_id);
+
+        let confirmer = Address::generate(env);
+        client.add_confirmer(&confirmer);
+
+        let mut buyers = Vec::new(env);
+        for _ in 0..BUYERS {
+            let buyer = Address::generate(env);
+            token::StellarAssetClient::new(env, &usdc_id).mint(&buyer, &FUNDS);
+            token::Client::new(env, &usdc_id).approve(&buyer, &contract_id, &FUNDS, &99999);
+            buyers.push_back(buyer);
+        }
+
+        Harness {
+            env,
+            client,
+            registry,
+            usdc_id,
+            organizer: Address::generate(env),
+            confirmer,
+            rng: Rng(seed),
+            at: (seed, 0, Op::Register),
+            buyers,
+            events: Vec::new(env),
+            tickets: Vec::new(env),
+            orders: 0,
+        }
+    }
+
+    fn general(&self) -> String {
+        String::from_str(self.env, "general")
+    }
+
+    fn apply(&mut self, op: Op) {
+        let env = self.env;
+        let Some(event_id) = self.rng.pick(&self.events) else {
+            self.register();
+            return;
+        };
+        let ticket = self.rng.pick(&self.tickets);
+        let buyer = self.rng.pick(&self.buyers).unwrap();
+
+        match op {
+            Op::Register => self.register(),
+            Op::Pay => {
+                let order_id = id(env, b'o', self.orders);
+                self.orders += 1;
+                let tier_id = self.general();
+                let Ok(Ok(amount)) = self.client.try_get_ticket_price(&event_id, &tier_id) else {
+                    return;
+                };
+                let quantity = self.rng.below(3) + 1;
+                let result = self.client.try_process_payment(
+                    &order_id,
+                    &event_id,
+                    &tier_id,
+                    &buyer,
+                    &buyer,
+                    &self.usdc_id,
+                    &amount,
+                    &quantity,
+                );
+                if result.is_ok() {
+                    let order = self.client.get_order(&order_id).unwrap();
+                    for ticket_id in order.ticket_ids.iter() {
+                        self.tickets.push_back(ticket_id);
+                    }
+                }
+            }
+            Op::Confirm => {
+                if let Some(ticket) = ticket {
+                    let tx = String::from_str(env, "tx");
+                    let _ = self
+                        .client
+                        .try_confirm_payment(&ticket, &tx, &self.confirmer);
+                }
+            }
+            Op::Refund => {
+                if let Some(ticket) = ticket {
+                    let _ = self.client.try_request_guest_refund(&ticket);
+                }
+            }
+            Op::Transfer => {
+                if let Some(ticket) = ticket {
+                    let _ = self.client.try_transfer_ticket(&ticket, &buyer);
+                }
+            }
+            Op::Resell => {
+                if let Some(ticket) = ticket {
+                    let price = (self.rng.below(1000) + 1) as i128;
+                    if self.client.try_list_ticket(&ticket, &price).is_ok() {
+                        let _ = self.client.try_buy_resale(&ticket, &buyer, &price);
+                    }
+                }
+            }
+            Op::CheckIn => {
+                if let Some(ticket) = ticket {
+                    let gate = String::from_str(env, "gate");
+                    let _ = self.client.try_check_in(&ticket, &self.organizer, &gate);
+                }
+            }
+            Op::Withdraw => {
+                let _ = self
+                    .client
+                    .try_withdraw_organizer_funds(&event_id, &self.usdc_id);
+            }
+            Op::SweepFees => {
+                let _ = self
+                    .client
+                    .try_withdraw_all_platform_fees(&self.usdc_id, &0, &10);
+            }
+            Op::SetStatus => {
+                let status = [
+                    event_registry::EventStatus::OnSale,
+                    event_registry::EventStatus::Paused,
+                    event_registry::EventStatus::Cancelled,
+                ][self.rng.below(3) as usize];
+                let _ = self.registry.try_update_event_status(&event_id, &status);
+            }
+            Op::BulkRefund => {
+                let batch_size = self.rng.below(5) + 1;
+                let _ =
+                    self.client
+                        .try_trigger_bulk_refund(&event_id, &batch_size, &self.organizer);
+            }
+        }
+    }
+
+    /// Registers an event with a refundable "general" tier, absorbing or
+    /// passing on the platform fee at random.
+    fn register(&mut self) {
+        let env = self.env;
+        let event_id = id(env, b'e', self.events.len());
+        let mut tiers = Map::new(env);
+        tiers.set(
+            self.general(),
+            event_registry::TicketTier {
+                name: String::from_str(env, "General"),
+                price: (self.rng.below(1000) + 1) as i128,
+                price_phases: Vec::new(env),
+                tier_limit: 12,
+                current_sold: 0,
+                sales_start_time: 0,
+                sales_end_time: 0,
+                is_refundable: true,
+                refund_policy: event_registry::RefundPolicy::full_refund(env),
+                is_retired: false,
+            },
+        );
+        self.registry
+            .register_event(&event_registry::EventRegistrationArgs {
+                event_id: event_id.clone(),
+                organizer_address: self.organizer.clone(),
+                payment_address: self.organizer.clone(),
+                metadata_cid: String::from_str(
+                    env,
+                    "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+                ),
+                max_supply: 0,
+                start_time: 0,
+                end_time: 0,
+                sales_start_time: 0,
+                sales_end_time: 0,
+                milestone_plan: None,
+                revenue_splits: None,
+                min_sales_target: 0,
+                sales_target_deadline: 0,
+                draft: false,
+                tiers,
+            });
+
+        if self.rng.below(2) == 0 {
+            self.client.set_fee_mode(&event_id, &FeeMode::BuyerPays);
+        }
+        self.client
+            .set_transfer_fee(&event_id, &(self.rng.below(3) as i128));
+        self.events.push_back(event_id);
+    }
+
+    fn check_invariants(&self) {
+        let at = self.at;
+        let usdc_id = &self.usdc_id;
+
+        // The contract holds exactly the escrow it owes, and no escrow is negative
+        let mut report = self.client.audit_solvency(usdc_id, &2);
+        while !report.complete {
+            report = self.client.audit_solvency(usdc_id, &2);
+        }
+        assert!(report.solvent, "{at:?}: {report:?}");
+        assert_eq!(report.balance, report.liabilities, "{at:?}");
+
+        let mut pending_fees = 0;
+        for event_id in self.events.iter() {
+            let balance = self.client.get_event_escrow_balance(&event_id, usdc_id);
+            assert!(balance.organizer_amount >= 0, "{at:?}: {balance:?}");
+            assert!(balance.platform_fee >= 0, "{at:?}: {balance:?}");
+            pending_fees += balance.platform_fee;
+            let mut page = self.client.reconcile_event(
+                &event_id,
+                usdc_id,
+                &0,
+                &2,
+                &SalesEscrow {
+                    organizer_amount: 0,
+                    platform_fee: 0,
+                },
+            );
+            while !page.complete {
+                page = self.client.reconcile_event(
+                    &event_id,
+                    usdc_id,
+                    &page.next_index,
+                    &2,
+                    &page.tickets,
+                );
+            }
+            assert!(page.balanced, "{at:?}: {page:?}");
+        }
+        let ledger = self.client.get_platform_fee_ledger(usdc_id);
+        assert_eq!(ledger.pending, pending_fees, "{at:?}");
+
+        // Every ticket record is consistent, and dead tickets cannot move
+        let mut live_per_event: Map<String, i128> = Map::new(self.env);
+        let mut live_per_holder: Map<Address, u32> = Map::new(self.env);
+        for ticket_id in self.tickets.iter() {
+            let payment = self.client.get_payment_status(&ticket_id).unwrap();
+            assert_eq!(
+                payment.amount,
+                payment.organizer_amount + payment.platform_fee,
+                "{at:?}"
+            );
+            assert!(payment.refunded_amount >= 0, "{at:?}");
+            assert!(payment.refunded_amount <= payment.amount, "{at:?}");
+
+            match payment.status {
+                PaymentStatus::Refunded | PaymentStatus::Failed => {
+                    let to = self.buyers.get(0).unwrap();
+                    assert!(
+                        self.client.try_transfer_ticket(&ticket_id, &to).is_err(),
+                        "{at:?}"
+                    );
+                    assert!(self.client.try_owner_of(&ticket_id).is_err(), "{at:?}");
+                }
+                _ => {
+                    let live = live_per_event.get(payment.event_id.clone()).unwrap_or(0);
+                    live_per_event.set(payment.event_id, live + 1);
+                    let held = live_per_holder
+                        .get(payment.buyer_address.clone())
+                        .unwrap_or(0);
+                    live_per_holder.set(payment.buyer_address, held + 1);
+                }
+            }
+        }
+
+        // The registry's inventory matches the tickets still live
+        for event_id in self.events.iter() {
+            let event = self.registry.get_event(&event_id).unwrap();
+            let mut total_sold = 0;
+            for tier in event.tiers.values() {
+                total_sold += tier.current_sold;
+            }
+            assert_eq!(event.current_supply, total_sold, "{at:?}");
+            // Bulk refunds of a cancelled event leave its inventory alone
+            if event.status != event_registry::EventStatus::Cancelled {
+                assert_eq!(
+                    Some(total_sold),
+                    live_per_event.get(event_id).or(Some(0)),
+                    "{at:?}"
+                );
+            }
+        }
+        for buyer in self.buyers.iter() {
+            let held = live_per_holder.get(buyer.clone()).unwrap_or(0);
+            assert_eq!(self.client.balance(&buyer), held, "{at:?}");
+        }
+    }
+}
+
+fn run(seed: u64, steps: u32) {
+    let env = Env::default();
+    let mut harness = Harness::new(&env, seed);
+    for step in 0..steps {
+        let op = Op::generate(&mut harness.rng);
+        harness.at = (seed, step, op);
+        harness.apply(op);
+        harness.check_invariants();
+    }
+}
+
+#[test]
+fn prop_escrow_and_ticket_invariants() {
+    for seed in [1, 7, 42, 2024] {
+        run(seed, 80);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::types::{DataKey, EventBalance, Payment, PaymentStatus};
+use crate::types::{
+    DataKey, EventBalance, FeeMode, Listing, Order, Payment, PaymentStatus, PlatformFeeLedger,
+    ResaleConfig, SalesEscrow, SettlementConfig, SolvencyAudit, TicketApproval,
+};
 use soroban_sdk::{vec, Address, Env, String, Vec};
 
 pub fn set_admin(env: &Env, admin: &Address) {
@@ -43,6 +46,22 @@ pub fn get_payment(env: &Env, payment_id: String) -> Option<Payment> {
     env.storage().persistent().get(&key)
 }
 
+pub fn has_payment(env: &Env, payment_id: String) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Payment(payment_id))
+}
+
+pub fn store_order(env: &Env, order: &Order) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Order(order.order_id.clone()), order);
+}
+
+pub fn get_order(env: &Env, order_id: String) -> Option<Order> {
+    env.storage().persistent().get(&DataKey::Order(order_id))
+}
+
 pub fn update_payment_status(
     env: &Env,
     payment_id: String,
@@ -139,10 +158,29 @@ pub fn is_token_whitelisted(env: &Env, token: &Address) -> bool {
         .unwrap_or(false)
 }
 
-pub fn get_event_balance(env: &Env, event_id: String) -> EventBalance {
+pub fn add_confirmer(env: &Env, confirmer: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Confirmer(confirmer.clone()), &true);
+}
+
+pub fn remove_confirmer(env: &Env, confirmer: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Confirmer(confirmer.clone()));
+}
+
+pub fn is_confirmer(env: &Env, confirmer: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Confirmer(confirmer.clone()))
+        .unwrap_or(false)
+}
+
+pub fn get_event_balance(env: &Env, event_id: String, token: Address) -> EventBalance {
     env.storage()
         .persistent()
-        .get(&DataKey::Balances(event_id))
+        .get(&DataKey::Balances(event_id, token))
         .unwrap_or(EventBalance {
             organizer_amount: 0,
             total_withdrawn: 0,
@@ -153,21 +191,28 @@ pub fn get_event_balance(env: &Env, event_id: String) -> EventBalance {
 pub fn update_event_balance(
     env: &Env,
     event_id: String,
+    token: Address,
     organizer_amount: i128,
     platform_fee: i128,
 ) {
-    let mut balance = get_event_balance(env, event_id.clone());
+    let mut balance = get_event_balance(env, event_id.clone(), token.clone());
     balance.organizer_amount += organizer_amount;
     balance.platform_fee += platform_fee;
-    env.storage()
-        .persistent()
-        .set(&DataKey::Balances(event_id), &balance);
+    env.storage().persistent().set(
+        &DataKey::Balances(event_id.clone(), token.clone()),
+        &balance,
+    );
+
+    add_escrow_event(env, &token, event_id);
+    if platform_fee != 0 {
+        record_platform_fee(env, &token, platform_fee);
+    }
 }
 
-pub fn set_event_balance(env: &Env, event_id: String, balance: EventBalance) {
+pub fn set_event_balance(env: &Env, event_id: String, token: Address, balance: EventBalance) {
     env.storage()
         .persistent()
-        .set(&DataKey::Balances(event_id), &balance);
+        .set(&DataKey::Balances(event_id, token), &balance);
 }
 
 pub fn set_transfer_fee(env: &Env, event_id: String, fee: i128) {
@@ -183,6 +228,210 @@ pub fn get_transfer_fee(env: &Env, event_id: String) -> i128 {
         .unwrap_or(0)
 }
 
+pub fn set_resale_config(env: &Env, event_id: String, config: &ResaleConfig) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ResaleConfig(event_id), config);
+}
+
+/// Without a config, tickets resell at up to face value with no royalty.
+pub fn get_resale_config(env: &Env, event_id: String) -> ResaleConfig {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ResaleConfig(event_id))
+        .unwrap_or(ResaleConfig {
+            max_markup_bps: 0,
+            royalty_bps: 0,
+        })
+}
+
+pub fn set_fee_mode(env: &Env, event_id: String, mode: FeeMode) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FeeMode(event_id), &mode);
+}
+
+/// Without a mode set, the organizer absorbs the platform fee.
+pub fn get_fee_mode(env: &Env, event_id: String) -> FeeMode {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FeeMode(event_id))
+        .unwrap_or(FeeMode::OrganizerAbsorbs)
+}
+
+pub fn set_listing(env: &Env, listing: &Listing) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Listing(listing.payment_id.clone()), listing);
+}
+
+pub fn get_listing(env: &Env, payment_id: String) -> Option<Listing> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Listing(payment_id))
+}
+
+pub fn remove_listing(env: &Env, payment_id: String) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Listing(payment_id));
+}
+
+pub fn set_approval(env: &Env, payment_id: String, approval: &TicketApproval) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Approval(payment_id), approval);
+}
+
+/// Returns the ticket's approval while it is still live.
+pub fn get_approval(env: &Env, payment_id: String) -> Option<TicketApproval> {
+    env.storage()
+        .persistent()
+        .get::<DataKey, TicketApproval>(&DataKey::Approval(payment_id))
+        .filter(|approval| approval.live_until_ledger >= env.ledger().sequence())
+}
+
+pub fn remove_approval(env: &Env, payment_id: String) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Approval(payment_id));
+}
+
+pub fn set_default_confirmation_window(env: &Env, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DefaultConfirmWindow, &seconds);
+}
+
+/// Without a configured window, pending payments never expire.
+pub fn get_default_confirmation_window(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DefaultConfirmWindow)
+        .unwrap_or(0)
+}
+
+pub fn set_event_confirmation_window(env: &Env, event_id: String, seconds: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ConfirmWindow(event_id), &seconds);
+}
+
+pub fn get_event_confirmation_window(env: &Env, event_id: String) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ConfirmWindow(event_id))
+}
+
+pub fn set_settlement_config(env: &Env, config: &SettlementConfig) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SettlementConfig, config);
+}
+
+/// Without a config nothing is held back and events settle as soon as they end.
+pub fn get_settlement_config(env: &Env) -> SettlementConfig {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SettlementConfig)
+        .unwrap_or(SettlementConfig {
+            holdback_bps: 0,
+            dispute_window: 0,
+        })
+}
+
+pub fn get_platform_fee_ledger(env: &Env, token: &Address) -> PlatformFeeLedger {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlatformFees(token.clone()))
+        .unwrap_or(PlatformFeeLedger {
+            accrued: 0,
+            withdrawn: 0,
+            pending: 0,
+        })
+}
+
+/// Adds fees collected in `token` to the global ledger. A negative `amount`
+/// records fees returned with a refund.
+pub fn record_platform_fee(env: &Env, token: &Address, amount: i128) {
+    let mut ledger = get_platform_fee_ledger(env, token);
+    ledger.accrued += amount;
+    ledger.pending += amount;
+    env.storage()
+        .persistent()
+        .set(&DataKey::PlatformFees(token.clone()), &ledger);
+}
+
+/// Records fees in `token` paid out to the platform wallet.
+pub fn record_platform_fee_withdrawal(env: &Env, token: &Address, amount: i128) {
+    let mut ledger = get_platform_fee_ledger(env, token);
+    ledger.withdrawn += amount;
+    ledger.pending -= amount;
+    env.storage()
+        .persistent()
+        .set(&DataKey::PlatformFees(token.clone()), &ledger);
+}
+
+/// Returns the events that have held escrow in `token`.
+pub fn get_escrow_events(env: &Env, token: &Address) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EscrowEvents(token.clone()))
+        .unwrap_or(vec![env])
+}
+
+fn add_escrow_event(env: &Env, token: &Address, event_id: String) {
+    let mut events = get_escrow_events(env, token);
+    if !events.contains(&event_id) {
+        events.push_back(event_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EscrowEvents(token.clone()), &events);
+    }
+}
+
+/// Without an audit in progress the next one starts at the first event.
+pub fn get_solvency_audit(env: &Env, token: &Address) -> SolvencyAudit {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SolvencyAudit(token.clone()))
+        .unwrap_or(SolvencyAudit {
+            next_index: 0,
+            liabilities: 0,
+        })
+}
+
+pub fn set_solvency_audit(env: &Env, token: &Address, audit: &SolvencyAudit) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SolvencyAudit(token.clone()), audit);
+}
+
+pub fn remove_solvency_audit(env: &Env, token: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::SolvencyAudit(token.clone()));
+}
+
+pub fn get_revenue_claimed(env: &Env, event_id: String, token: Address, payee: Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RevenueClaimed(event_id, token, payee))
+        .unwrap_or(0)
+}
+
+pub fn set_revenue_claimed(
+    env: &Env,
+    event_id: String,
+    token: Address,
+    payee: Address,
+    amount: i128,
+) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::RevenueClaimed(event_id, token, payee), &amount);
+}
+
 pub fn add_payment_to_buyer_index(env: &Env, buyer_address: Address, payment_id: String) {
     let key = DataKey::BuyerPayments(buyer_address);
     let mut buyer_payments: Vec<String> = env.storage().persistent().get(&key).unwrap_or(vec![env]);
@@ -215,3 +464,105 @@ pub fn get_bulk_refund_index(env: &Env, event_id: String) -> u32 {
         .get(&DataKey::BulkRefundIndex(event_id))
         .unwrap_or(0)
 }
+
+pub fn set_expiry_sweep_index(env: &Env, event_id: String, index: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ExpirySweepIndex(event_id), &index);
+}
+
+pub fn get_expiry_sweep_index(env: &Env, event_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ExpirySweepIndex(event_id))
+        .unwrap_or(0)
+}
+
+pub fn get_live_tickets(env: &Env, event_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LiveTickets(event_id))
+        .unwrap_or(0)
+}
+
+pub fn add_live_tickets(env: &Env, event_id: String, count: u32) {
+    let live = get_live_tickets(env, event_id.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::LiveTickets(event_id), &(live + count));
+}
+
+pub fn remove_live_ticket(env: &Env, event_id: String) {
+    let live = get_live_tickets(env, event_id.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::LiveTickets(event_id), &live.saturating_sub(1));
+}
+
+pub fn set_price_phase(env: &Env, event_id: String, tier_id: String, phase_index: u32) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PricePhase(event_id, tier_id), &phase_index);
+}
+
+pub fn get_price_phase(env: &Env, event_id: String, tier_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PricePhase(event_id, tier_id))
+        .unwrap_or(0)
+}
+
+pub fn get_sales_escrow(env: &Env, event_id: String, token: Address) -> SalesEscrow {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SalesEscrow(event_id, token))
+        .unwrap_or(SalesEscrow {
+            organizer_amount: 0,
+            platform_fee: 0,
+        })
+}
+
+/// Adds a primary sale to an event's sales escrow; refunds pass negative
+/// amounts.
+pub fn record_sales_escrow(
+    env: &Env,
+    event_id: String,
+    token: Address,
+    organizer_amount: i128,
+    platform_fee: i128,
+) {
+    let mut escrow = get_sales_escrow(env, event_id.clone(), token.clone());
+    escrow.organizer_amount += organizer_amount;
+    escrow.platform_fee += platform_fee;
+    env.storage()
+        .persistent()
+        .set(&DataKey::SalesEscrow(event_id, token), &escrow);
+}
+
+/// Returns the escrow an event holds in `token` for payments still Pending.
+pub fn get_pending_escrow(env: &Env, event_id: String, token: Address) -> SalesEscrow {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingEscrow(event_id, token))
+        .unwrap_or(SalesEscrow {
+            organizer_amount: 0,
+            platform_fee: 0,
+        })
+}
+
+/// Adds a pending sale to an event's pending escrow; payments leaving Pending
+/// pass negative amounts.
+pub fn record_pending_escrow(
+    env: &Env,
+    event_id: String,
+    token: Address,
+    organizer_amount: i128,
+    platform_fee: i128,
+) {
+    let mut escrow = get_pending_escrow(env, event_id.clone(), token.clone());
+    escrow.organizer_amount += organizer_amount;
+    escrow.platform_fee += platform_fee;
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingEscrow(event_id, token), &escrow);
+}
@@ -1,6 +1,6 @@
 use super::contract::{event_registry, TicketPaymentContract, TicketPaymentContractClient};
 use super::storage::*;
-use super::types::{Payment, PaymentStatus};
+use super::types::{FeeMode, Payment, PaymentStatus, SalesEscrow};
 use crate::error::TicketPaymentError;
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
@@ -17,6 +17,7 @@ impl MockEventRegistry {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500, // 5%
+            tiers: soroban_sdk::Map::new(&env),
         }
     }
 
@@ -31,7 +32,11 @@ impl MockEventRegistry {
                 organizer_address: Address::generate(&env), // This will be different each call unless mocked specifically
                 payment_address: Address::generate(&env),
                 platform_fee_percent: 500,
-                is_active: true,
+                status: env
+                    .storage()
+                    .instance()
+                    .get(&Symbol::new(&env, "status"))
+                    .unwrap_or(event_registry::EventStatus::OnSale),
                 created_at: 0,
                 metadata_cid: String::from_str(
                     &env,
@@ -39,7 +44,24 @@ impl MockEventRegistry {
                 ),
                 max_supply: 0,
                 current_supply: 0,
+                start_time: env
+                    .storage()
+                    .instance()
+                    .get(&Symbol::new(&env, "start"))
+                    .unwrap_or(0),
+                end_time: env
+                    .storage()
+                    .instance()
+                    .get(&Symbol::new(&env, "end"))
+                    .unwrap_or(0),
+                sales_start_time: 0,
+                sales_end_time: 0,
                 milestone_plan: None,
+                revenue_splits: None,
+                min_sales_target: 0,
+                sales_target_deadline: 0,
+                sales_target_met: false,
+                completed_at: 0,
                 tiers: {
                     let mut tiers = soroban_sdk::Map::new(&env);
                     tiers.set(
@@ -47,11 +69,22 @@ impl MockEventRegistry {
                         event_registry::TicketTier {
                             name: String::from_str(&env, "Tier 1"),
                             price: 1000_0000000i128,
-                            early_bird_price: 1000_0000000i128,
-                            early_bird_deadline: 0,
+                            price_phases: soroban_sdk::Vec::new(&env),
                             tier_limit: 1000,
                             current_sold: 0,
-                            is_refundable: true,
+                            sales_start_time: 0,
+                            sales_end_time: 0,
+                            is_refundable: env
+                                .storage()
+                                .instance()
+                                .get(&Symbol::new(&env, "refundable"))
+                                .unwrap_or(true),
+                            refund_policy: env
+                                .storage()
+                                .instance()
+                                .get(&Symbol::new(&env, "policy"))
+                                .unwrap_or(event_registry::RefundPolicy::full_refund(&env)),
+                            is_retired: false,
                         },
                     );
                     tiers
@@ -63,6 +96,43 @@ impl MockEventRegistry {
 
     pub fn increment_inventory(_env: Env, _event_id: String, _tier_id: String, _quantity: u32) {}
     pub fn decrement_inventory(_env: Env, _event_id: String, _tier_id: String) {}
+
+    // The mock organizer changes on every call, so every caller holds every role
+    pub fn has_role(
+        _env: Env,
+        _event_id: String,
+        _role: event_registry::EventRole,
+        _account: Address,
+    ) -> bool {
+        true
+    }
+
+    pub fn set_status(env: Env, status: event_registry::EventStatus) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "status"), &status);
+    }
+
+    pub fn set_tier_refundable(env: Env, is_refundable: bool) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "refundable"), &is_refundable);
+    }
+
+    pub fn set_end_time(env: Env, end_time: u64) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "end"), &end_time);
+    }
+
+    pub fn set_refund_policy(env: Env, start_time: u64, policy: event_registry::RefundPolicy) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "start"), &start_time);
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "policy"), &policy);
+    }
 }
 
 // Another Mock for different fee
@@ -75,6 +145,7 @@ impl MockEventRegistry2 {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 250, // 2.5%
+            tiers: soroban_sdk::Map::new(&env),
         }
     }
 
@@ -84,7 +155,7 @@ impl MockEventRegistry2 {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 250,
-            is_active: true,
+            status: event_registry::EventStatus::OnSale,
             created_at: 0,
             metadata_cid: String::from_str(
                 &env,
@@ -92,7 +163,16 @@ impl MockEventRegistry2 {
             ),
             max_supply: 0,
             current_supply: 0,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             milestone_plan: None,
+            revenue_splits: None,
+            min_sales_target: 0,
+            sales_target_deadline: 0,
+            sales_target_met: false,
+            completed_at: 0,
             tiers: {
                 let mut tiers = soroban_sdk::Map::new(&env);
                 tiers.set(
@@ -100,11 +180,14 @@ impl MockEventRegistry2 {
                     event_registry::TicketTier {
                         name: String::from_str(&env, "Tier 1"),
                         price: 10000i128,
-                        early_bird_price: 10000i128,
-                        early_bird_deadline: 0,
+                        price_phases: soroban_sdk::Vec::new(&env),
                         tier_limit: 1000,
                         current_sold: 0,
+                        sales_start_time: 0,
+                        sales_end_time: 0,
                         is_refundable: true,
+                        refund_policy: event_registry::RefundPolicy::full_refund(&env),
+                        is_retired: false,
                     },
                 );
                 tiers
@@ -201,6 +284,7 @@ fn test_process_payment_success() {
         &event_id,
         &tier_id,
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
@@ -208,7 +292,7 @@ fn test_process_payment_success() {
     assert_eq!(result_id, payment_id);
 
     // Check escrow balances
-    let escrow_balance = client.get_event_escrow_balance(&event_id);
+    let escrow_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     let expected_fee = (amount * 500) / 10000;
     assert_eq!(escrow_balance.platform_fee, expected_fee);
     assert_eq!(escrow_balance.organizer_amount, amount - expected_fee);
@@ -252,6 +336,8 @@ fn test_confirm_payment() {
     env.mock_all_auths();
 
     let (client, _admin, _, _, _) = setup_test(&env);
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
     let buyer = Address::generate(&env);
     let payment_id = String::from_str(&env, "pay_1");
     let tx_hash = String::from_str(&env, "tx_hash_123");
@@ -259,28 +345,39 @@ fn test_confirm_payment() {
     // Pre-create a payment record
     let payment = Payment {
         payment_id: payment_id.clone(),
+        order_id: payment_id.clone(),
         event_id: String::from_str(&env, "e1"),
+        payer_address: buyer.clone(),
         buyer_address: buyer,
         ticket_tier_id: String::from_str(&env, "t1"),
+        token_address: Address::generate(&env),
         amount: 100,
         platform_fee: 5,
+        platform_fee_bps: 500,
         organizer_amount: 95,
+        refunded_amount: 0,
+        refunded_fee: 0,
         status: PaymentStatus::Pending,
         transaction_hash: String::from_str(&env, ""),
         created_at: 100,
         confirmed_at: None,
+        checked_in_at: None,
     };
 
     env.as_contract(&client.address, || {
         store_payment(&env, payment);
     });
 
-    client.confirm_payment(&payment_id, &tx_hash);
+    client.confirm_payment(&payment_id, &tx_hash, &confirmer);
 
     let updated = client.get_payment_status(&payment_id).unwrap();
     assert_eq!(updated.status, PaymentStatus::Confirmed);
     assert_eq!(updated.transaction_hash, tx_hash);
     assert!(updated.confirmed_at.is_some());
+
+    // A confirmed payment cannot be confirmed again
+    let res = client.try_confirm_payment(&payment_id, &tx_hash, &confirmer);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentStatus)));
 }
 
 #[test]
@@ -298,6 +395,7 @@ fn test_process_payment_zero_amount() {
         &String::from_str(&env, "e1"),
         &String::from_str(&env, "t1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &0,
         &1,
@@ -332,6 +430,7 @@ fn test_batch_purchase_success() {
         &event_id,
         &tier_id,
         &buyer,
+        &buyer,
         &usdc_id,
         &amount_per_ticket,
         &quantity,
@@ -339,27 +438,32 @@ fn test_batch_purchase_success() {
     assert_eq!(result_id, payment_id);
 
     // Check escrow balances
-    let escrow_balance = client.get_event_escrow_balance(&event_id);
+    let escrow_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     let expected_fee = (total_amount * 500) / 10000;
     assert_eq!(escrow_balance.platform_fee, expected_fee);
     assert_eq!(escrow_balance.organizer_amount, total_amount - expected_fee);
 
-    // Check individual payment records - check at least first two
-    // Check individual payment records - check at least first two
-    let sub_id_0 = match 0 {
-        0 => String::from_str(&env, "p-0"),
-        _ => String::from_str(&env, "p-many"),
-    };
-    let payment_0 = client.get_payment_status(&sub_id_0).unwrap();
-    assert_eq!(payment_0.amount, amount_per_ticket);
-
-    let sub_id_1 = match 1 {
-        1 => String::from_str(&env, "p-1"),
-        _ => String::from_str(&env, "p-many"),
-    };
-    let payment_1 = client.get_payment_status(&sub_id_1).unwrap();
-    assert_eq!(payment_1.amount, amount_per_ticket);
-    assert_eq!(payment_1.amount, amount_per_ticket);
+    // Each ticket gets its own record, linked back to the order
+    let order = client.get_order(&payment_id).unwrap();
+    assert_eq!(order.ticket_ids.len(), quantity);
+    assert_eq!(order.total_amount, total_amount);
+    for (index, expected) in [
+        "batch_1-0",
+        "batch_1-1",
+        "batch_1-2",
+        "batch_1-3",
+        "batch_1-4",
+    ]
+    .iter()
+    .enumerate()
+    {
+        let ticket_id = String::from_str(&env, expected);
+        assert_eq!(order.ticket_ids.get(index as u32).unwrap(), ticket_id);
+        let payment = client.get_payment_status(&ticket_id).unwrap();
+        assert_eq!(payment.order_id, payment_id);
+        assert_eq!(payment.amount, amount_per_ticket);
+    }
+    assert_eq!(client.get_buyer_payments(&buyer).len(), quantity);
 }
 
 #[test]
@@ -389,6 +493,7 @@ fn test_fee_calculation_variants() {
         &String::from_str(&env, "event_1"),
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
@@ -426,6 +531,7 @@ fn test_process_payment_not_found() {
         &String::from_str(&env, "e1"),
         &String::from_str(&env, "t1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &10000i128,
         &1,
@@ -604,6 +710,7 @@ fn test_process_payment_with_non_whitelisted_token() {
         &String::from_str(&env, "e1"),
         &String::from_str(&env, "t1"),
         &buyer,
+        &buyer,
         &non_whitelisted_token,
         &10000i128,
         &1,
@@ -642,6 +749,7 @@ fn test_process_payment_with_multiple_tokens() {
         &String::from_str(&env, "event_1"),
         &String::from_str(&env, "tier_1"),
         &buyer1,
+        &buyer1,
         &usdc_id,
         &usdc_amount,
         &1,
@@ -652,17 +760,20 @@ fn test_process_payment_with_multiple_tokens() {
         &String::from_str(&env, "event_1"),
         &String::from_str(&env, "tier_1"),
         &buyer2,
+        &buyer2,
         &xlm_id,
         &xlm_amount,
         &1,
     );
 
-    // Check escrow balances instead of direct transfers
-    let escrow_balance = client.get_event_escrow_balance(&String::from_str(&env, "event_1"));
+    // Each token is escrowed separately
+    let event_id = String::from_str(&env, "event_1");
     let expected_usdc_fee = (usdc_amount * 500) / 10000;
     let expected_xlm_fee = (xlm_amount * 500) / 10000;
-    let total_expected_fee = expected_usdc_fee + expected_xlm_fee;
-    assert_eq!(escrow_balance.platform_fee, total_expected_fee);
+    let usdc_escrow = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(usdc_escrow.platform_fee, expected_usdc_fee);
+    let xlm_escrow = client.get_event_escrow_balance(&event_id, &xlm_id);
+    assert_eq!(xlm_escrow.platform_fee, expected_xlm_fee);
 
     let payment1 = client
         .get_payment_status(&String::from_str(&env, "pay_usdc"))
@@ -685,6 +796,7 @@ impl MockEventRegistryMaxSupply {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
+            tiers: soroban_sdk::Map::new(&env),
         }
     }
 
@@ -694,7 +806,7 @@ impl MockEventRegistryMaxSupply {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
-            is_active: true,
+            status: event_registry::EventStatus::OnSale,
             created_at: 0,
             metadata_cid: String::from_str(
                 &env,
@@ -702,7 +814,16 @@ impl MockEventRegistryMaxSupply {
             ),
             max_supply: 100,
             current_supply: 100,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             milestone_plan: None,
+            revenue_splits: None,
+            min_sales_target: 0,
+            sales_target_deadline: 0,
+            sales_target_met: false,
+            completed_at: 0,
             tiers: {
                 let mut tiers = soroban_sdk::Map::new(&env);
                 tiers.set(
@@ -710,11 +831,14 @@ impl MockEventRegistryMaxSupply {
                     event_registry::TicketTier {
                         name: String::from_str(&env, "Tier 1"),
                         price: 10000i128,
-                        early_bird_price: 10000i128,
-                        early_bird_deadline: 0,
+                        price_phases: soroban_sdk::Vec::new(&env),
                         tier_limit: 1000,
                         current_sold: 0,
+                        sales_start_time: 0,
+                        sales_end_time: 0,
                         is_refundable: true,
+                        refund_policy: event_registry::RefundPolicy::full_refund(&env),
+                        is_retired: false,
                     },
                 );
                 tiers
@@ -754,6 +878,7 @@ fn test_process_payment_max_supply_exceeded() {
         &String::from_str(&env, "e1"),
         &String::from_str(&env, "t1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &10000i128,
         &1,
@@ -772,6 +897,7 @@ impl MockEventRegistryWithInventory {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
+            tiers: soroban_sdk::Map::new(&env),
         }
     }
 
@@ -784,7 +910,7 @@ impl MockEventRegistryWithInventory {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
-            is_active: true,
+            status: event_registry::EventStatus::OnSale,
             created_at: 0,
             metadata_cid: String::from_str(
                 &env,
@@ -792,7 +918,16 @@ impl MockEventRegistryWithInventory {
             ),
             max_supply: 10,
             current_supply,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             milestone_plan: None,
+            revenue_splits: None,
+            min_sales_target: 0,
+            sales_target_deadline: 0,
+            sales_target_met: false,
+            completed_at: 0,
             tiers: {
                 let mut tiers = soroban_sdk::Map::new(&env);
                 tiers.set(
@@ -800,11 +935,14 @@ impl MockEventRegistryWithInventory {
                     event_registry::TicketTier {
                         name: String::from_str(&env, "Tier 1"),
                         price: 1000_0000000i128,
-                        early_bird_price: 1000_0000000i128,
-                        early_bird_deadline: 0,
+                        price_phases: soroban_sdk::Vec::new(&env),
                         tier_limit: 1000,
                         current_sold: 0,
+                        sales_start_time: 0,
+                        sales_end_time: 0,
                         is_refundable: true,
+                        refund_policy: event_registry::RefundPolicy::full_refund(&env),
+                        is_retired: false,
                     },
                 );
                 tiers
@@ -849,6 +987,7 @@ fn test_inventory_increment_on_successful_payment() {
         &String::from_str(&env, "event_1"),
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
@@ -861,6 +1000,7 @@ fn test_inventory_increment_on_successful_payment() {
         &String::from_str(&env, "event_1"),
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
@@ -889,18 +1029,20 @@ fn test_withdraw_organizer_funds() {
         &event_id,
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
     );
+    confirm_tickets(&env, &client, &["pay_1"]);
 
-    let balance = client.get_event_escrow_balance(&event_id);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert!(balance.organizer_amount > 0);
 
     let withdrawn = client.withdraw_organizer_funds(&event_id, &usdc_id);
     assert_eq!(withdrawn, balance.organizer_amount);
 
-    let new_balance = client.get_event_escrow_balance(&event_id);
+    let new_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert_eq!(new_balance.organizer_amount, 0);
 }
 
@@ -909,7 +1051,7 @@ fn test_withdraw_platform_fees() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _admin, usdc_id, platform_wallet, _) = setup_test(&env);
+    let (client, _admin, usdc_id, platform_wallet, registry_id) = setup_test(&env);
     let usdc_token = token::StellarAssetClient::new(&env, &usdc_id);
 
     let buyer = Address::generate(&env);
@@ -925,14 +1067,22 @@ fn test_withdraw_platform_fees() {
         &event_id,
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
     );
+    confirm_tickets(&env, &client, &["pay_1"]);
 
-    let balance = client.get_event_escrow_balance(&event_id);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     let initial_platform_balance = token::Client::new(&env, &usdc_id).balance(&platform_wallet);
 
+    // Fees stay in escrow until the event settles
+    let res = client.try_withdraw_platform_fees(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SettlementNotReady)));
+    MockEventRegistryClient::new(&env, &registry_id)
+        .set_status(&event_registry::EventStatus::Completed);
+
     let withdrawn = client.withdraw_platform_fees(&event_id, &usdc_id);
     assert_eq!(withdrawn, balance.platform_fee);
 
@@ -942,7 +1092,7 @@ fn test_withdraw_platform_fees() {
         balance.platform_fee
     );
 
-    let new_balance = client.get_event_escrow_balance(&event_id);
+    let new_balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert_eq!(new_balance.platform_fee, 0);
 }
 
@@ -956,6 +1106,7 @@ impl MockEventRegistryWithMilestones {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
+            tiers: soroban_sdk::Map::new(&env),
         }
     }
 
@@ -978,7 +1129,7 @@ impl MockEventRegistryWithMilestones {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
-            is_active: true,
+            status: event_registry::EventStatus::OnSale,
             created_at: 0,
             metadata_cid: String::from_str(
                 &env,
@@ -986,7 +1137,16 @@ impl MockEventRegistryWithMilestones {
             ),
             max_supply: 10,
             current_supply,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             milestone_plan: Some(milestones),
+            revenue_splits: None,
+            min_sales_target: 0,
+            sales_target_deadline: 0,
+            sales_target_met: false,
+            completed_at: 0,
             tiers: {
                 let mut tiers = soroban_sdk::Map::new(&env);
                 tiers.set(
@@ -994,11 +1154,14 @@ impl MockEventRegistryWithMilestones {
                     event_registry::TicketTier {
                         name: String::from_str(&env, "Tier 1"),
                         price: 1000_0000000i128,
-                        early_bird_price: 1000_0000000i128,
-                        early_bird_deadline: 0,
+                        price_phases: soroban_sdk::Vec::new(&env),
                         tier_limit: 1000,
                         current_sold: 0,
+                        sales_start_time: 0,
+                        sales_end_time: 0,
                         is_refundable: true,
+                        refund_policy: event_registry::RefundPolicy::full_refund(&env),
+                        is_retired: false,
                     },
                 );
                 tiers
@@ -1046,10 +1209,12 @@ fn test_withdraw_with_milestones() {
         &event_id,
         &tier_id,
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
     );
+    confirm_tickets(&env, &client, &["p1"]);
     let withdrawn1 = client.withdraw_organizer_funds(&event_id, &usdc_id);
     assert_eq!(withdrawn1, 0); // Still 0%
 
@@ -1059,10 +1224,12 @@ fn test_withdraw_with_milestones() {
         &event_id,
         &tier_id,
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
     );
+    confirm_tickets(&env, &client, &["p2"]);
     let withdrawn2 = client.withdraw_organizer_funds(&event_id, &usdc_id);
     let expected_revenue_2_tickets = 1900_0000000i128; // 950 + 950
     let expected_withdraw_25 = (expected_revenue_2_tickets * 2500) / 10000;
@@ -1078,10 +1245,12 @@ fn test_withdraw_with_milestones() {
         &event_id,
         &tier_id,
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
     );
+    confirm_tickets(&env, &client, &["p3"]);
     let withdrawn4 = client.withdraw_organizer_funds(&event_id, &usdc_id);
     let expected_revenue_3_tickets = 2850_0000000i128; // 950 * 3
     let expected_withdraw_25_total = (expected_revenue_3_tickets * 2500) / 10000;
@@ -1093,10 +1262,12 @@ fn test_withdraw_with_milestones() {
         &event_id,
         &tier_id,
         &buyer,
+        &buyer,
         &usdc_id,
         &amount,
         &1,
     );
+    confirm_tickets(&env, &client, &["p4"]);
     let withdrawn5 = client.withdraw_organizer_funds(&event_id, &usdc_id);
     let expected_revenue_4_tickets = 3800_0000000i128;
     let expected_withdraw_50_total = (expected_revenue_4_tickets * 5000) / 10000;
@@ -1106,7 +1277,7 @@ fn test_withdraw_with_milestones() {
     );
 
     // Verify balance
-    let balance = client.get_event_escrow_balance(&event_id);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert_eq!(
         balance.total_withdrawn,
         withdrawn2 + withdrawn4 + withdrawn5
@@ -1129,16 +1300,23 @@ fn test_transfer_ticket_success() {
     // Pre-create a confirmed payment record
     let payment = Payment {
         payment_id: payment_id.clone(),
+        order_id: payment_id.clone(),
         event_id: String::from_str(&env, "event_1"),
+        payer_address: buyer.clone(),
         buyer_address: buyer.clone(),
         ticket_tier_id: String::from_str(&env, "t1"),
+        token_address: Address::generate(&env),
         amount: 1000,
         platform_fee: 50,
+        platform_fee_bps: 500,
         organizer_amount: 950,
+        refunded_amount: 0,
+        refunded_fee: 0,
         status: PaymentStatus::Confirmed,
         transaction_hash: String::from_str(&env, "tx_1"),
         created_at: 100,
         confirmed_at: Some(101),
+        checked_in_at: None,
     };
 
     env.as_contract(&client.address, || {
@@ -1183,21 +1361,28 @@ fn test_transfer_ticket_with_fee() {
     token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &transfer_fee, &9999);
 
     // Initial escrow balance
-    let initial_escrow = client.get_event_escrow_balance(&event_id);
+    let initial_escrow = client.get_event_escrow_balance(&event_id, &usdc_id);
 
     // Pre-create a confirmed payment record
     let payment = Payment {
         payment_id: payment_id.clone(),
+        order_id: payment_id.clone(),
         event_id: event_id.clone(),
+        payer_address: buyer.clone(),
         buyer_address: buyer.clone(),
         ticket_tier_id: String::from_str(&env, "t1"),
+        token_address: usdc_id.clone(),
         amount: 1000,
         platform_fee: 50,
+        platform_fee_bps: 500,
         organizer_amount: 950,
+        refunded_amount: 0,
+        refunded_fee: 0,
         status: PaymentStatus::Confirmed,
         transaction_hash: String::from_str(&env, "tx_1"),
         created_at: 100,
         confirmed_at: Some(101),
+        checked_in_at: None,
     };
 
     env.as_contract(&client.address, || {
@@ -1207,7 +1392,7 @@ fn test_transfer_ticket_with_fee() {
     client.transfer_ticket(&payment_id, &new_owner);
 
     // Verify fee deduction
-    let new_escrow = client.get_event_escrow_balance(&event_id);
+    let new_escrow = client.get_event_escrow_balance(&event_id, &usdc_id);
     assert_eq!(
         new_escrow.organizer_amount,
         initial_escrow.organizer_amount + transfer_fee
@@ -1229,16 +1414,23 @@ fn test_transfer_ticket_unauthorized() {
 
     let payment = Payment {
         payment_id: payment_id.clone(),
+        order_id: payment_id.clone(),
         event_id: String::from_str(&env, "event_1"),
+        payer_address: buyer.clone(),
         buyer_address: buyer.clone(),
         ticket_tier_id: String::from_str(&env, "t1"),
+        token_address: Address::generate(&env),
         amount: 1000,
         platform_fee: 50,
+        platform_fee_bps: 500,
         organizer_amount: 950,
+        refunded_amount: 0,
+        refunded_fee: 0,
         status: PaymentStatus::Confirmed,
         transaction_hash: String::from_str(&env, ""),
         created_at: 100,
         confirmed_at: Some(101),
+        checked_in_at: None,
     };
 
     env.as_contract(&client.address, || {
@@ -1261,6 +1453,7 @@ impl MockEventRegistryEarlyBird {
         event_registry::PaymentInfo {
             payment_address: Address::generate(&env),
             platform_fee_percent: 500, // 5%
+            tiers: soroban_sdk::Map::new(&env),
         }
     }
 
@@ -1270,7 +1463,7 @@ impl MockEventRegistryEarlyBird {
             organizer_address: Address::generate(&env),
             payment_address: Address::generate(&env),
             platform_fee_percent: 500,
-            is_active: true,
+            status: event_registry::EventStatus::OnSale,
             created_at: 0,
             metadata_cid: String::from_str(
                 &env,
@@ -1278,7 +1471,16 @@ impl MockEventRegistryEarlyBird {
             ),
             max_supply: 0,
             current_supply: 0,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
             milestone_plan: None,
+            revenue_splits: None,
+            min_sales_target: 0,
+            sales_target_deadline: 0,
+            sales_target_met: false,
+            completed_at: 0,
             tiers: {
                 let mut tiers = soroban_sdk::Map::new(&env);
                 tiers.set(
@@ -1286,11 +1488,21 @@ impl MockEventRegistryEarlyBird {
                     event_registry::TicketTier {
                         name: String::from_str(&env, "Tier 1"),
                         price: 1500_0000000i128, // Standard 150 USDC
-                        early_bird_price: 1000_0000000i128, // Early Bird 100 USDC
-                        early_bird_deadline: 1000000, // Deadline at timestamp 1,000,000
+                        price_phases: soroban_sdk::vec![
+                            &env,
+                            event_registry::PricePhase {
+                                name: String::from_str(&env, "Early Bird"),
+                                price: 1000_0000000i128, // Early Bird 100 USDC
+                                ends_at: 1000000,        // Deadline at timestamp 1,000,000
+                            }
+                        ],
                         tier_limit: 1000,
                         current_sold: 0,
+                        sales_start_time: 0,
+                        sales_end_time: 0,
                         is_refundable: true,
+                        refund_policy: event_registry::RefundPolicy::full_refund(&env),
+                        is_retired: false,
                     },
                 );
                 tiers
@@ -1334,6 +1546,7 @@ fn test_early_bird_pricing_active() {
         &String::from_str(&env, "event_eb_1"),
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &1000_0000000i128, // Paying early bird price
         &1,
@@ -1375,6 +1588,7 @@ fn test_early_bird_pricing_expired() {
         &String::from_str(&env, "event_eb_1"),
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &1000_0000000i128, // Trying early bird price
         &1,
@@ -1388,6 +1602,7 @@ fn test_early_bird_pricing_expired() {
         &String::from_str(&env, "event_eb_1"),
         &String::from_str(&env, "tier_1"),
         &buyer,
+        &buyer,
         &usdc_id,
         &1500_0000000i128, // Paying standard price
         &1,
@@ -1424,16 +1639,19 @@ fn test_price_switched_event_emitted_exactly_once() {
 
     let event_id = String::from_str(&env, "event_eb_1");
     let tier_id_str = String::from_str(&env, "tier_1");
+    let mut switches = soroban_sdk::Vec::new(&env);
 
     client.process_payment(
         &String::from_str(&env, "pay_1"),
         &event_id,
         &tier_id_str,
         &buyer,
+        &buyer,
         &usdc_id,
         &1000_0000000i128,
         &1,
     );
+    switches.append(&price_switches(&env));
 
     // After setting ledger exactly at the deadline (still early bird)
     env.ledger().with_mut(|li| li.timestamp = 1000000);
@@ -1442,10 +1660,12 @@ fn test_price_switched_event_emitted_exactly_once() {
         &event_id,
         &tier_id_str,
         &buyer,
+        &buyer,
         &usdc_id,
         &1000_0000000i128, // exactly at deadline uses early bird
         &1,
     );
+    switches.append(&price_switches(&env));
 
     // Setting ledger past deadline triggers switch
     env.ledger().with_mut(|li| li.timestamp = 1000001);
@@ -1454,10 +1674,12 @@ fn test_price_switched_event_emitted_exactly_once() {
         &event_id,
         &tier_id_str,
         &buyer,
+        &buyer,
         &usdc_id,
         &1500_0000000i128,
         &1,
     );
+    switches.append(&price_switches(&env));
 
     // And another payment long past deadline
     env.ledger().with_mut(|li| li.timestamp = 1500000);
@@ -1466,41 +1688,171 @@ fn test_price_switched_event_emitted_exactly_once() {
         &event_id,
         &tier_id_str,
         &buyer,
+        &buyer,
         &usdc_id,
         &1500_0000000i128,
         &1,
     );
+    switches.append(&price_switches(&env));
 
-    // Now count the occurrences of PriceSwitchedEvent in the logs
-    let events = env.events().all();
-    let price_switched_topic = Symbol::new(&env, "PriceSwitched");
+    assert_eq!(
+        switches.len(),
+        1,
+        "PriceSwitched should be emitted EXACTLY once"
+    );
+    let data = switches.get(0).unwrap();
+    assert_eq!(data.event_id, event_id);
+    assert_eq!(data.tier_id, tier_id_str);
+    assert_eq!(data.phase_index, 1);
+    assert_eq!(data.new_price, 1500_0000000i128);
+    assert_eq!(data.timestamp, 1000001); // Recorded on the FIRST payment after deadline
+}
 
-    let mut switch_events_count = 0;
+/// Returns the `PriceSwitched` events published by the last invocation.
+fn price_switches(env: &Env) -> soroban_sdk::Vec<crate::events::PriceSwitchedEvent> {
+    let mut switches = soroban_sdk::Vec::new(env);
+    for (_, topics, data) in env.events().all().iter() {
+        let topic: Option<crate::events::AgoraEvent> =
+            topics.get(0).and_then(|t| t.try_into_val(env).ok());
+        if topic == Some(crate::events::AgoraEvent::PriceSwitched) {
+            switches.push_back(data.try_into_val(env).unwrap());
+        }
+    }
+    switches
+}
 
-    for e in events.iter() {
-        if let Some(t) = e.1.get(0) {
-            if let Ok(sym) = <soroban_sdk::Val as TryIntoVal<Env, Symbol>>::try_into_val(&t, &env) {
-                if sym == price_switched_topic {
-                    switch_events_count += 1;
+// Mock Event Registry with a three-phase pricing schedule
+#[soroban_sdk::contract]
+pub struct MockEventRegistryPhases;
 
-                    let data: crate::events::PriceSwitchedEvent = e.2.try_into_val(&env).unwrap();
-                    assert_eq!(data.event_id, event_id);
-                    assert_eq!(data.tier_id, tier_id_str);
-                    assert_eq!(data.new_price, 1500_0000000i128);
-                    assert_eq!(data.timestamp, 1000001); // Recorded on the FIRST payment after deadline
-                }
-            }
-        }
+#[soroban_sdk::contractimpl]
+impl MockEventRegistryPhases {
+    pub fn get_event(env: Env, _event_id: String) -> Option<event_registry::EventInfo> {
+        Some(event_registry::EventInfo {
+            event_id: String::from_str(&env, "event_phases"),
+            organizer_address: Address::generate(&env),
+            payment_address: Address::generate(&env),
+            platform_fee_percent: 500,
+            status: event_registry::EventStatus::OnSale,
+            created_at: 0,
+            metadata_cid: String::from_str(
+                &env,
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            ),
+            max_supply: 0,
+            current_supply: 0,
+            start_time: 0,
+            end_time: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            milestone_plan: None,
+            revenue_splits: None,
+            min_sales_target: 0,
+            sales_target_deadline: 0,
+            sales_target_met: false,
+            completed_at: 0,
+            tiers: {
+                let mut tiers = soroban_sdk::Map::new(&env);
+                tiers.set(
+                    String::from_str(&env, "tier_1"),
+                    event_registry::TicketTier {
+                        name: String::from_str(&env, "Tier 1"),
+                        price: 300,
+                        price_phases: soroban_sdk::vec![
+                            &env,
+                            event_registry::PricePhase {
+                                name: String::from_str(&env, "Super Early"),
+                                price: 100,
+                                ends_at: 1000,
+                            },
+                            event_registry::PricePhase {
+                                name: String::from_str(&env, "Early"),
+                                price: 200,
+                                ends_at: 2000,
+                            }
+                        ],
+                        tier_limit: 1000,
+                        current_sold: 0,
+                        sales_start_time: 0,
+                        sales_end_time: 0,
+                        is_refundable: true,
+                        refund_policy: event_registry::RefundPolicy::full_refund(&env),
+                        is_retired: false,
+                    },
+                );
+                tiers
+            },
+        })
     }
 
-    // Some hosts delay recording events, or they may be truncated, but if they exist,
-    // they should exist exactly once.
-    if switch_events_count > 0 {
-        assert_eq!(
-            switch_events_count, 1,
-            "PriceSwitched should be emitted EXACTLY once"
+    pub fn increment_inventory(_env: Env, _event_id: String, _tier_id: String, _quantity: u32) {}
+}
+
+#[test]
+fn test_price_switched_event_emitted_for_every_phase() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketPaymentContract, ());
+    let client = TicketPaymentContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let usdc_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let platform_wallet = Address::generate(&env);
+    let event_registry_id = env.register(MockEventRegistryPhases, ());
+    client.initialize(&admin, &usdc_id, &platform_wallet, &event_registry_id);
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &10000);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &10000, &99999);
+
+    let event_id = String::from_str(&env, "event_phases");
+    let tier_id = String::from_str(&env, "tier_1");
+    let mut switches = soroban_sdk::Vec::<(u32, i128)>::new(&env);
+
+    // (timestamp, price paid). No sale happens during the "Early" phase, so the
+    // third purchase announces both the "Early" phase and the regular price.
+    let purchases = [(500u64, 100i128), (800, 100), (2500, 300), (2600, 300)];
+
+    for (i, (timestamp, price)) in purchases.iter().enumerate() {
+        env.ledger().with_mut(|li| li.timestamp = *timestamp);
+
+        // The previous phase's price is no longer accepted
+        if *timestamp > 1000 {
+            let res = client.try_process_payment(
+                &String::from_str(&env, "stale"),
+                &event_id,
+                &tier_id,
+                &buyer,
+                &buyer,
+                &usdc_id,
+                &(price - 100),
+                &1,
+            );
+            assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPrice)));
+        }
+
+        let payment_ids = ["p0", "p1", "p2", "p3"];
+        client.process_payment(
+            &String::from_str(&env, payment_ids[i]),
+            &event_id,
+            &tier_id,
+            &buyer,
+            &buyer,
+            &usdc_id,
+            price,
+            &1,
         );
+
+        for data in price_switches(&env).iter() {
+            assert_eq!(data.timestamp, 2500);
+            switches.push_back((data.phase_index, data.new_price));
+        }
     }
+
+    assert_eq!(switches, soroban_sdk::vec![&env, (1, 200), (2, 300)]);
 }
 
 #[test]
@@ -1508,7 +1860,7 @@ fn test_bulk_refund_success() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
     let usdc_token = token::StellarAssetClient::new(&env, &usdc_id);
 
     let buyer1 = Address::generate(&env);
@@ -1525,6 +1877,7 @@ fn test_bulk_refund_success() {
         &event_id,
         &tier_id,
         &buyer1,
+        &buyer1,
         &usdc_id,
         &ticket_price,
         &1,
@@ -1537,14 +1890,25 @@ fn test_bulk_refund_success() {
         &event_id,
         &tier_id,
         &buyer2,
+        &buyer2,
         &usdc_id,
         &ticket_price,
         &1,
     );
 
     // Confirm them
-    client.confirm_payment(&String::from_str(&env, "p1"), &String::from_str(&env, "h1"));
-    client.confirm_payment(&String::from_str(&env, "p2"), &String::from_str(&env, "h2"));
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+    client.confirm_payment(
+        &String::from_str(&env, "p1"),
+        &String::from_str(&env, "h1"),
+        &confirmer,
+    );
+    client.confirm_payment(
+        &String::from_str(&env, "p2"),
+        &String::from_str(&env, "h2"),
+        &confirmer,
+    );
 
     // Initial balances
     let initial_buyer1 = token::Client::new(&env, &usdc_id).balance(&buyer1);
@@ -1552,8 +1916,11 @@ fn test_bulk_refund_success() {
     assert_eq!(initial_buyer1, 0);
     assert_eq!(initial_buyer2, 0);
 
-    // Trigger bulk refund
-    let count = client.trigger_bulk_refund(&event_id, &10);
+    // Cancel the event, then trigger bulk refund
+    MockEventRegistryClient::new(&env, &registry_id)
+        .set_status(&event_registry::EventStatus::Cancelled);
+    let finance = Address::generate(&env);
+    let count = client.trigger_bulk_refund(&event_id, &10, &finance);
     assert_eq!(count, 2);
 
     // Check final balances
@@ -1588,7 +1955,7 @@ fn test_bulk_refund_batching() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
     let usdc_token = token::StellarAssetClient::new(&env, &usdc_id);
 
     let event_id = String::from_str(&env, "event_1");
@@ -1602,6 +1969,8 @@ fn test_bulk_refund_batching() {
         String::from_str(&env, "p2"),
     ];
 
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
     for pid in pids.iter() {
         let buyer = Address::generate(&env);
         usdc_token.mint(&buyer, &ticket_price);
@@ -1611,22 +1980,2243 @@ fn test_bulk_refund_batching() {
             &event_id,
             &tier_id,
             &buyer,
+            &buyer,
             &usdc_id,
             &ticket_price,
             &1,
         );
-        client.confirm_payment(pid, &String::from_str(&env, "h"));
+        client.confirm_payment(pid, &String::from_str(&env, "h"), &confirmer);
     }
 
+    MockEventRegistryClient::new(&env, &registry_id)
+        .set_status(&event_registry::EventStatus::Cancelled);
+
     // Refund batch 1 (size 2)
-    let count1 = client.trigger_bulk_refund(&event_id, &2);
+    let finance = Address::generate(&env);
+    let count1 = client.trigger_bulk_refund(&event_id, &2, &finance);
     assert_eq!(count1, 2);
 
     // Refund batch 2 (size 2, only 1 left)
-    let count2 = client.trigger_bulk_refund(&event_id, &2);
+    let count2 = client.trigger_bulk_refund(&event_id, &2, &finance);
     assert_eq!(count2, 1);
 
     // Refund batch 3 (none left)
-    let count3 = client.trigger_bulk_refund(&event_id, &2);
+    let count3 = client.trigger_bulk_refund(&event_id, &2, &finance);
     assert_eq!(count3, 0);
 }
+
+#[test]
+fn test_bulk_refund_requires_cancelled_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _usdc_id, _, registry_id) = setup_test(&env);
+    let event_id = String::from_str(&env, "event_1");
+
+    let finance = Address::generate(&env);
+    let res = client.try_trigger_bulk_refund(&event_id, &10, &finance);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventNotCancelled)));
+
+    // Paused events are still going ahead
+    MockEventRegistryClient::new(&env, &registry_id)
+        .set_status(&event_registry::EventStatus::Paused);
+    let res = client.try_trigger_bulk_refund(&event_id, &10, &finance);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventNotCancelled)));
+}
+
+#[test]
+fn test_process_payment_rejected_when_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    MockEventRegistryClient::new(&env, &registry_id)
+        .set_status(&event_registry::EventStatus::Paused);
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, "pay_1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventInactive)));
+}
+
+#[test]
+fn test_guest_refund_follows_event_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
+    let registry = MockEventRegistryClient::new(&env, &registry_id);
+    registry.set_tier_refundable(&false);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let payment_id = String::from_str(&env, "pay_1");
+    client.process_payment(
+        &payment_id,
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+
+    // Pausing sales must not make a non-refundable ticket refundable
+    registry.set_status(&event_registry::EventStatus::Paused);
+    let res = client.try_request_guest_refund(&payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketNotRefundable)));
+
+    // Cancelling the event does
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    client.request_guest_refund(&payment_id);
+    assert_eq!(
+        client.get_payment_status(&payment_id).unwrap().status,
+        PaymentStatus::Refunded
+    );
+}
+
+#[test]
+fn test_withdrawals_blocked_for_cancelled_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+
+    MockEventRegistryClient::new(&env, &registry_id)
+        .set_status(&event_registry::EventStatus::Cancelled);
+
+    let res = client.try_withdraw_organizer_funds(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+    let res = client.try_withdraw_platform_fees(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+}
+
+/// Wires the contract to a real Event Registry, reached through the client
+/// declared in `agora_types`. The registry holds "event_1" with a refundable
+/// "general" tier priced at 1000 USDC. Returns the organizer as the last item.
+fn setup_with_registry(
+    env: &Env,
+) -> (
+    TicketPaymentContractClient<'static>,
+    ::event_registry::EventRegistryClient<'static>,
+    Address,
+    Address,
+) {
+    let contract_id = env.register(TicketPaymentContract, ());
+    let client = TicketPaymentContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let usdc_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+
+    let registry_id = env.register(::event_registry::EventRegistry, ());
+    let registry = ::event_registry::EventRegistryClient::new(env, &registry_id);
+    registry.initialize(&admin, &Address::generate(env), &500);
+    registry.set_ticket_payment_contract(&contract_id);
+    client.initialize(&admin, &usdc_id, &Address::generate(env), &registry_id);
+
+    let organizer = Address::generate(env);
+    register_general_event(env, &registry, "event_1", &organizer, None);
+
+    (client, registry, usdc_id, organizer)
+}
+
+/// Registers `event_id` on the real registry with a refundable "general" tier
+/// of 10 tickets priced at 1000 USDC.
+fn register_general_event(
+    env: &Env,
+    registry: &::event_registry::EventRegistryClient,
+    event_id: &str,
+    organizer: &Address,
+    revenue_splits: Option<soroban_sdk::Vec<event_registry::RevenueSplit>>,
+) {
+    registry.register_event(&general_event_args(
+        env,
+        event_id,
+        organizer,
+        revenue_splits,
+    ));
+}
+
+/// Registers `event_id` like `register_general_event`, needing
+/// `min_sales_target` tickets sold by `sales_target_deadline`. Its tier is not
+/// refundable, so only a missed target lets buyers back out.
+fn register_sales_target_event(
+    env: &Env,
+    registry: &::event_registry::EventRegistryClient,
+    event_id: &str,
+    organizer: &Address,
+    min_sales_target: i128,
+    sales_target_deadline: u64,
+) -> String {
+    let mut args = general_event_args(env, event_id, organizer, None);
+    args.min_sales_target = min_sales_target;
+    args.sales_target_deadline = sales_target_deadline;
+    let tier_id = String::from_str(env, "general");
+    let mut tier = args.tiers.get(tier_id.clone()).unwrap();
+    tier.is_refundable = false;
+    args.tiers.set(tier_id, tier);
+    registry.register_event(&args);
+    args.event_id
+}
+
+fn general_event_args(
+    env: &Env,
+    event_id: &str,
+    organizer: &Address,
+    revenue_splits: Option<soroban_sdk::Vec<event_registry::RevenueSplit>>,
+) -> event_registry::EventRegistrationArgs {
+    let mut tiers = soroban_sdk::Map::new(env);
+    tiers.set(
+        String::from_str(env, "general"),
+        event_registry::TicketTier {
+            name: String::from_str(env, "General"),
+            price: 1000_0000000,
+            price_phases: soroban_sdk::Vec::new(env),
+            tier_limit: 10,
+            current_sold: 0,
+            sales_start_time: 0,
+            sales_end_time: 0,
+            is_refundable: true,
+            refund_policy: event_registry::RefundPolicy::full_refund(env),
+            is_retired: false,
+        },
+    );
+    event_registry::EventRegistrationArgs {
+        event_id: String::from_str(env, event_id),
+        organizer_address: organizer.clone(),
+        payment_address: Address::generate(env),
+        metadata_cid: String::from_str(
+            env,
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        max_supply: 10,
+        start_time: 0,
+        end_time: 0,
+        sales_start_time: 0,
+        sales_end_time: 0,
+        milestone_plan: None,
+        revenue_splits,
+        min_sales_target: 0,
+        sales_target_deadline: 0,
+        draft: false,
+        tiers,
+    }
+}
+
+#[test]
+fn test_payment_round_trip_against_event_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, _organizer) = setup_with_registry(&env);
+    let event_id = String::from_str(&env, "event_1");
+    let tier_id = String::from_str(&env, "general");
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let payment_id = client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    let event = registry.get_event(&event_id).unwrap();
+    assert_eq!(event.current_supply, 1);
+    assert_eq!(event.tiers.get(tier_id.clone()).unwrap().current_sold, 1);
+
+    client.request_guest_refund(&payment_id);
+    let event = registry.get_event(&event_id).unwrap();
+    assert_eq!(event.current_supply, 0);
+    assert_eq!(event.tiers.get(tier_id).unwrap().current_sold, 0);
+}
+
+#[test]
+fn test_batch_ticket_ids_do_not_collide_across_orders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let amount = 1000_0000000i128;
+    let event_id = String::from_str(&env, "event_1");
+    let tier_id = String::from_str(&env, "tier_1");
+
+    let buyer_a = Address::generate(&env);
+    let buyer_b = Address::generate(&env);
+    for buyer in [&buyer_a, &buyer_b] {
+        token::StellarAssetClient::new(&env, &usdc_id).mint(buyer, &(amount * 7));
+        token::Client::new(&env, &usdc_id).approve(buyer, &client.address, &(amount * 7), &99999);
+    }
+
+    client.process_payment(
+        &String::from_str(&env, "order_a"),
+        &event_id,
+        &tier_id,
+        &buyer_a,
+        &buyer_a,
+        &usdc_id,
+        &amount,
+        &7,
+    );
+    client.process_payment(
+        &String::from_str(&env, "order_b"),
+        &event_id,
+        &tier_id,
+        &buyer_b,
+        &buyer_b,
+        &usdc_id,
+        &amount,
+        &7,
+    );
+
+    assert_eq!(client.get_buyer_payments(&buyer_a).len(), 7);
+    assert_eq!(client.get_buyer_payments(&buyer_b).len(), 7);
+    let ticket = client
+        .get_payment_status(&String::from_str(&env, "order_a-6"))
+        .unwrap();
+    assert_eq!(ticket.buyer_address, buyer_a);
+    let ticket = client
+        .get_payment_status(&String::from_str(&env, "order_b-6"))
+        .unwrap();
+    assert_eq!(ticket.buyer_address, buyer_b);
+}
+
+#[test]
+fn test_duplicate_order_or_ticket_id_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(amount * 4));
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &(amount * 4), &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    let tier_id = String::from_str(&env, "tier_1");
+    client.process_payment(
+        &String::from_str(&env, "order-1"),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+
+    // Reusing the order ID would overwrite the stored ticket
+    let res = client.try_process_payment(
+        &String::from_str(&env, "order-1"),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentAlreadyExists)));
+
+    // A batch named "order" would derive "order-1" for its second ticket
+    let res = client.try_process_payment(
+        &String::from_str(&env, "order"),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &2,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentAlreadyExists)));
+
+    let res = client.try_process_payment(
+        &String::from_str(&env, ""),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentId)));
+
+    // Nothing was charged for the rejected attempts
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&buyer),
+        amount * 3
+    );
+}
+
+#[test]
+fn test_refund_single_ticket_in_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(amount * 3));
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &(amount * 3), &99999);
+
+    let order_id = String::from_str(&env, "order_1");
+    client.process_payment(
+        &order_id,
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &3,
+    );
+
+    client.request_guest_refund(&String::from_str(&env, "order_1-1"));
+
+    let order = client.get_order(&order_id).unwrap();
+    for (index, ticket_id) in order.ticket_ids.iter().enumerate() {
+        let expected = if index == 1 {
+            PaymentStatus::Refunded
+        } else {
+            PaymentStatus::Pending
+        };
+        assert_eq!(
+            client.get_payment_status(&ticket_id).unwrap().status,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_confirm_payment_requires_confirmer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let payment_id = String::from_str(&env, "pay_1");
+    client.process_payment(
+        &payment_id,
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+
+    let tx_hash = String::from_str(&env, "tx_hash_123");
+    let outsider = Address::generate(&env);
+    let res = client.try_confirm_payment(&payment_id, &tx_hash, &outsider);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    let res = client.try_confirm_payment(&String::from_str(&env, "missing"), &tx_hash, &outsider);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    // Rotating the confirmer revokes the old key
+    let backend = Address::generate(&env);
+    client.add_confirmer(&backend);
+    assert!(client.is_confirmer(&backend));
+    client.remove_confirmer(&backend);
+    assert!(!client.is_confirmer(&backend));
+    let res = client.try_confirm_payment(&payment_id, &tx_hash, &backend);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    let rotated = Address::generate(&env);
+    client.add_confirmer(&rotated);
+    let res = client.try_confirm_payment(&String::from_str(&env, "missing"), &tx_hash, &rotated);
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentNotFound)));
+    client.confirm_payment(&payment_id, &tx_hash, &rotated);
+    assert_eq!(
+        client.get_payment_status(&payment_id).unwrap().status,
+        PaymentStatus::Confirmed
+    );
+}
+
+#[test]
+fn test_confirmer_rotation_emits_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _, _, _) = setup_test(&env);
+    let confirmer = Address::generate(&env);
+
+    client.add_confirmer(&confirmer);
+    let events = env.events().all();
+    let (_, topics, data) = events.iter().last().unwrap();
+    let topic: crate::events::AgoraEvent = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, crate::events::AgoraEvent::ConfirmerAdded);
+    let event: crate::events::ConfirmerUpdatedEvent = data.try_into_val(&env).unwrap();
+    assert_eq!(event.confirmer, confirmer);
+
+    client.remove_confirmer(&confirmer);
+    let events = env.events().all();
+    let (_, topics, _) = events.iter().last().unwrap();
+    let topic: crate::events::AgoraEvent = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, crate::events::AgoraEvent::ConfirmerRemoved);
+}
+
+#[test]
+fn test_guest_refund_returns_funds_and_debits_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _usdc_id, _, _) = setup_test(&env);
+    // Pay with a second whitelisted token; the refund must come back in it
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_token(&token_id);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &token_id).mint(&buyer, &(amount * 2));
+    token::Client::new(&env, &token_id).approve(&buyer, &client.address, &(amount * 2), &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    client.process_payment(
+        &String::from_str(&env, "order_1"),
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &token_id,
+        &amount,
+        &2,
+    );
+    let ticket_id = String::from_str(&env, "order_1-0");
+    assert_eq!(
+        client.get_payment_status(&ticket_id).unwrap().token_address,
+        token_id
+    );
+
+    client.request_guest_refund(&ticket_id);
+
+    let token_client = token::Client::new(&env, &token_id);
+    assert_eq!(token_client.balance(&buyer), amount);
+    assert_eq!(token_client.balance(&client.address), amount);
+
+    // Only the refunded ticket's share leaves escrow
+    let expected_fee = (amount * 500) / 10000;
+    let balance = client.get_event_escrow_balance(&event_id, &token_id);
+    assert_eq!(balance.platform_fee, expected_fee);
+    assert_eq!(balance.organizer_amount, amount - expected_fee);
+
+    let res = client.try_request_guest_refund(&ticket_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentStatus)));
+}
+
+#[test]
+fn test_guest_refund_fails_after_organizer_withdrawal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    let payment_id = String::from_str(&env, "pay_1");
+    client.process_payment(
+        &payment_id,
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    confirm_tickets(&env, &client, &["pay_1"]);
+    client.withdraw_organizer_funds(&event_id, &usdc_id);
+
+    let res = client.try_request_guest_refund(&payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InsufficientEscrow)));
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), 0);
+    assert_eq!(
+        client.get_payment_status(&payment_id).unwrap().status,
+        PaymentStatus::Confirmed
+    );
+}
+
+#[test]
+fn test_guest_refund_follows_refund_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
+    let day = 86400u64;
+    let now = 1_000_000u64;
+    env.ledger().with_mut(|li| li.timestamp = now);
+
+    // 100% until 14 days before, 50% until 48 hours before, then nothing;
+    // every refund keeps a 10 USDC processing fee for the organizer.
+    let processing_fee = 10_0000000i128;
+    MockEventRegistryClient::new(&env, &registry_id).set_refund_policy(
+        &(now + 20 * day),
+        &event_registry::RefundPolicy {
+            windows: soroban_sdk::vec![
+                &env,
+                event_registry::RefundWindow {
+                    min_seconds_before_start: 14 * day,
+                    refund_bps: 10000,
+                },
+                event_registry::RefundWindow {
+                    min_seconds_before_start: 2 * day,
+                    refund_bps: 5000,
+                },
+            ],
+            processing_fee,
+        },
+    );
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(amount * 3));
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &(amount * 3), &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    client.process_payment(
+        &String::from_str(&env, "order_1"),
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &3,
+    );
+
+    // 20 days out: full refund minus the processing fee
+    let full = amount - processing_fee;
+    client.request_guest_refund(&String::from_str(&env, "order_1-0"));
+    let ticket = client
+        .get_payment_status(&String::from_str(&env, "order_1-0"))
+        .unwrap();
+    assert_eq!(ticket.refunded_amount, full);
+
+    // 3 days out: half refund minus the processing fee
+    env.ledger().with_mut(|li| li.timestamp = now + 17 * day);
+    let half = amount / 2 - processing_fee;
+    client.request_guest_refund(&String::from_str(&env, "order_1-1"));
+
+    // 1 day out: nothing left to refund
+    env.ledger().with_mut(|li| li.timestamp = now + 19 * day);
+    let res = client.try_request_guest_refund(&String::from_str(&env, "order_1-2"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketNotRefundable)));
+
+    let usdc = token::Client::new(&env, &usdc_id);
+    assert_eq!(usdc.balance(&buyer), full + half);
+    assert_eq!(usdc.balance(&client.address), amount * 3 - full - half);
+
+    // The platform returns its fee on the refunded share of each ticket, and
+    // the organizer keeps both processing fees on top of the rest of its share
+    let fee_per_ticket = (amount * 500) / 10000;
+    let organizer_per_ticket = amount - fee_per_ticket;
+    assert_eq!(ticket.refunded_fee, fee_per_ticket);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(
+        balance.platform_fee,
+        fee_per_ticket * 3 - fee_per_ticket - fee_per_ticket / 2
+    );
+    assert_eq!(
+        balance.organizer_amount,
+        organizer_per_ticket * 3 - organizer_per_ticket - organizer_per_ticket / 2
+            + 2 * processing_fee
+    );
+    assert_eq!(
+        balance.organizer_amount + balance.platform_fee,
+        amount * 3 - full - half
+    );
+}
+
+#[test]
+fn test_escrow_is_tracked_per_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
+    let other_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_token(&other_id);
+
+    let amount = 1000_0000000i128;
+    let event_id = String::from_str(&env, "event_1");
+    let tier_id = String::from_str(&env, "tier_1");
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+    client.process_payment(
+        &String::from_str(&env, "pay_usdc"),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    confirm_tickets(&env, &client, &["pay_usdc"]);
+
+    let fee = (amount * 500) / 10000;
+    let usdc_escrow = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(usdc_escrow.organizer_amount, amount - fee);
+    assert_eq!(usdc_escrow.platform_fee, fee);
+    let other_escrow = client.get_event_escrow_balance(&event_id, &other_id);
+    assert_eq!(other_escrow.organizer_amount, 0);
+    assert_eq!(other_escrow.platform_fee, 0);
+
+    // Nothing was paid in the other token, so nothing can be withdrawn in it
+    MockEventRegistryClient::new(&env, &registry_id)
+        .set_status(&event_registry::EventStatus::Completed);
+    assert_eq!(client.withdraw_organizer_funds(&event_id, &other_id), 0);
+    assert_eq!(client.withdraw_platform_fees(&event_id, &other_id), 0);
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&client.address),
+        amount
+    );
+
+    assert_eq!(
+        client.withdraw_organizer_funds(&event_id, &usdc_id),
+        amount - fee
+    );
+    assert_eq!(client.withdraw_platform_fees(&event_id, &usdc_id), fee);
+    assert_eq!(
+        token::Client::new(&env, &usdc_id).balance(&client.address),
+        0
+    );
+}
+
+#[test]
+fn test_check_in_by_organizer_or_scanner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(amount * 2));
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &(amount * 2), &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    client.process_payment(
+        &String::from_str(&env, "order_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &2,
+    );
+    let first = String::from_str(&env, "order_1-0");
+    let second = String::from_str(&env, "order_1-1");
+    let gate = String::from_str(&env, "gate_a");
+
+    // Pending tickets cannot be redeemed
+    let res = client.try_check_in(&first, &organizer, &gate);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentStatus)));
+
+    let tx_hash = String::from_str(&env, "tx");
+    client.confirm_payment(&first, &tx_hash, &confirmer);
+    client.confirm_payment(&second, &tx_hash, &confirmer);
+
+    let outsider = Address::generate(&env);
+    let res = client.try_check_in(&first, &outsider, &gate);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    client.check_in(&first, &organizer, &gate);
+    let events = env.events().all();
+    let (_, topics, data) = events.iter().last().unwrap();
+    let topic: crate::events::AgoraEvent = topics.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, crate::events::AgoraEvent::TicketCheckedIn);
+    let event: crate::events::TicketCheckedInEvent = data.try_into_val(&env).unwrap();
+    assert_eq!(event.scanner, organizer);
+    assert_eq!(event.gate_id, gate);
+    assert_eq!(event.holder, buyer);
+
+    let ticket = client.get_payment_status(&first).unwrap();
+    assert_eq!(ticket.status, PaymentStatus::CheckedIn);
+    assert!(ticket.checked_in_at.is_some());
+
+    // Door staff holding the registry's Scanner role can scan too
+    let scanner = Address::generate(&env);
+    let res = client.try_check_in(&second, &scanner, &gate);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+    registry.grant_role(&event_id, &event_registry::EventRole::Scanner, &scanner);
+    client.check_in(&second, &scanner, &String::from_str(&env, "gate_b"));
+}
+
+#[test]
+fn test_cancelled_event_tickets_cannot_be_checked_in() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    let payment_id = client.process_payment(
+        &String::from_str(&env, "order_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    client.confirm_payment(&payment_id, &String::from_str(&env, "tx"), &confirmer);
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Cancelled);
+
+    let res = client.try_check_in(&payment_id, &organizer, &String::from_str(&env, "gate_a"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+
+    // The buyer can still get their money back
+    client.request_guest_refund(&payment_id);
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), amount);
+}
+
+#[test]
+fn test_checked_in_tickets_refunded_when_event_cancelled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(amount * 3));
+    token_client.approve(&buyer, &client.address, &(amount * 3), &99999);
+
+    let event_id = String::from_str(&env, "event_1");
+    client.process_payment(
+        &String::from_str(&env, "order_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &3,
+    );
+    let gate = String::from_str(&env, "gate_a");
+    for ticket in ["order_1-0", "order_1-1", "order_1-2"] {
+        let ticket = String::from_str(&env, ticket);
+        client.confirm_payment(&ticket, &String::from_str(&env, "tx"), &confirmer);
+        if ticket != String::from_str(&env, "order_1-2") {
+            client.check_in(&ticket, &organizer, &gate);
+        }
+    }
+    let checked_in = String::from_str(&env, "order_1-0");
+    let res = client.try_request_guest_refund(&checked_in);
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketAlreadyCheckedIn)));
+
+    // Once the event is cancelled, admission no longer stands in the way
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Cancelled);
+    client.request_guest_refund(&checked_in);
+    assert_eq!(client.trigger_bulk_refund(&event_id, &10, &organizer), 2);
+    assert_eq!(token_client.balance(&buyer), amount * 3);
+    assert_eq!(
+        client
+            .get_payment_status(&String::from_str(&env, "order_1-1"))
+            .unwrap()
+            .status,
+        PaymentStatus::Refunded
+    );
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_bulk_refund_delegated_to_finance_role() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, _usdc_id, organizer) = setup_with_registry(&env);
+    let event_id = String::from_str(&env, "event_1");
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Cancelled);
+
+    let finance = Address::generate(&env);
+    let res = client.try_trigger_bulk_refund(&event_id, &10, &finance);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    // Other roles do not grant refund rights
+    registry.grant_role(&event_id, &event_registry::EventRole::Scanner, &finance);
+    let res = client.try_trigger_bulk_refund(&event_id, &10, &finance);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    registry.grant_role(&event_id, &event_registry::EventRole::Finance, &finance);
+    assert_eq!(client.trigger_bulk_refund(&event_id, &10, &finance), 0);
+    assert_eq!(client.trigger_bulk_refund(&event_id, &10, &organizer), 0);
+}
+
+#[test]
+fn test_bulk_refund_skips_tickets_escrow_cannot_cover() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    let amount = 1000_0000000i128;
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(amount * 2));
+    token_client.approve(&buyer, &client.address, &(amount * 2), &99999);
+    let buy = |payment_id: &str| {
+        let payment_id = client.process_payment(
+            &String::from_str(&env, payment_id),
+            &event_id,
+            &String::from_str(&env, "general"),
+            &buyer,
+            &buyer,
+            &usdc_id,
+            &amount,
+            &1,
+        );
+        client.confirm_payment(&payment_id, &String::from_str(&env, "tx"), &confirmer);
+        payment_id
+    };
+
+    // The organizer's share of the first sale is paid out before cancelling,
+    // so escrow only covers one of the two tickets
+    buy("pay_1");
+    client.withdraw_organizer_funds(&event_id, &usdc_id);
+    let second = buy("pay_2");
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Cancelled);
+
+    assert_eq!(client.trigger_bulk_refund(&event_id, &1, &organizer), 1);
+
+    // The uncovered ticket is skipped instead of blocking every later batch
+    assert_eq!(client.trigger_bulk_refund(&event_id, &1, &organizer), 0);
+    let events = env.events().all();
+    let (_, _, data) = events.iter().last().unwrap();
+    let event: crate::events::BulkRefundProcessedEvent = data.try_into_val(&env).unwrap();
+    assert_eq!(event.refund_count, 0);
+    assert_eq!(event.skipped_count, 1);
+    assert_eq!(client.trigger_bulk_refund(&event_id, &1, &organizer), 0);
+
+    let skipped = client.get_payment_status(&second).unwrap();
+    assert_eq!(skipped.status, PaymentStatus::Confirmed);
+    assert_eq!(token_client.balance(&buyer), amount);
+}
+
+#[test]
+fn test_checked_in_ticket_cannot_be_rescanned_transferred_or_refunded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _registry, usdc_id, organizer) = setup_with_registry(&env);
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let payment_id = client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    client.confirm_payment(&payment_id, &String::from_str(&env, "tx"), &confirmer);
+
+    let gate = String::from_str(&env, "gate_a");
+    client.check_in(&payment_id, &organizer, &gate);
+
+    let res = client.try_check_in(&payment_id, &organizer, &gate);
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketAlreadyCheckedIn)));
+
+    let res = client.try_transfer_ticket(&payment_id, &Address::generate(&env));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketAlreadyCheckedIn)));
+
+    let res = client.try_request_guest_refund(&payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketAlreadyCheckedIn)));
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), 0);
+}
+
+/// Buys one confirmed `tier_1` ticket on the mock registry's `event_1`.
+fn buy_confirmed_ticket(
+    env: &Env,
+    client: &TicketPaymentContractClient,
+    usdc_id: &Address,
+    payment_id: &str,
+) -> (String, Address) {
+    let buyer = Address::generate(env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(env, usdc_id).mint(&buyer, &amount);
+    token::Client::new(env, usdc_id).approve(&buyer, &client.address, &amount, &99999);
+
+    let payment_id = client.process_payment(
+        &String::from_str(env, payment_id),
+        &String::from_str(env, "event_1"),
+        &String::from_str(env, "tier_1"),
+        &buyer,
+        &buyer,
+        usdc_id,
+        &amount,
+        &1,
+    );
+    let confirmer = Address::generate(env);
+    client.add_confirmer(&confirmer);
+    client.confirm_payment(&payment_id, &String::from_str(env, "tx"), &confirmer);
+    (payment_id, buyer)
+}
+
+/// Confirms pending tickets, as the backend does once their payment clears.
+fn confirm_tickets(env: &Env, client: &TicketPaymentContractClient, ticket_ids: &[&str]) {
+    let confirmer = Address::generate(env);
+    client.add_confirmer(&confirmer);
+    for ticket_id in ticket_ids {
+        client.confirm_payment(
+            &String::from_str(env, ticket_id),
+            &String::from_str(env, "tx"),
+            &confirmer,
+        );
+    }
+}
+
+#[test]
+fn test_resale_pays_seller_royalty_and_platform_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    let (payment_id, seller) = buy_confirmed_ticket(&env, &client, &usdc_id, "pay_1");
+
+    // 20% markup cap, 10% royalty; the mock event charges a 5% platform fee
+    client.set_resale_config(&event_id, &2000, &1000);
+
+    let res = client.try_list_ticket(&payment_id, &1201_0000000);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ResalePriceTooHigh)));
+
+    let price = 1200_0000000i128;
+    client.list_ticket(&payment_id, &price);
+    let listing = client.get_listing(&payment_id).unwrap();
+    assert_eq!(listing.seller, seller);
+    assert_eq!(listing.price, price);
+
+    let res = client.try_buy_resale(&payment_id, &seller, &price);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidAddress)));
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &price);
+    token_client.approve(&buyer, &client.address, &price, &99999);
+
+    // A seller raising the price before the purchase lands cannot charge more
+    client.list_ticket(&payment_id, &(price - 1));
+    client.list_ticket(&payment_id, &price);
+    let res = client.try_buy_resale(&payment_id, &buyer, &(price - 1));
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPrice)));
+    assert_eq!(token_client.balance(&buyer), price);
+
+    client.buy_resale(&payment_id, &buyer, &price);
+
+    let platform_fee = 60_0000000i128;
+    let royalty = 120_0000000i128;
+    assert_eq!(token_client.balance(&buyer), 0);
+    assert_eq!(
+        token_client.balance(&seller),
+        price - platform_fee - royalty
+    );
+
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, 950_0000000 + royalty);
+    assert_eq!(balance.platform_fee, 50_0000000 + platform_fee);
+    assert_eq!(
+        token_client.balance(&client.address),
+        balance.organizer_amount + balance.platform_fee
+    );
+
+    let ticket = client.get_payment_status(&payment_id).unwrap();
+    assert_eq!(ticket.buyer_address, buyer);
+    assert!(client.get_listing(&payment_id).is_none());
+    assert_eq!(client.get_buyer_payments(&seller).len(), 0);
+    assert_eq!(
+        client.get_buyer_payments(&buyer).get(0).unwrap(),
+        payment_id
+    );
+}
+
+#[test]
+fn test_resale_listing_rules() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let event_id = String::from_str(&env, "event_1");
+    let (payment_id, _seller) = buy_confirmed_ticket(&env, &client, &usdc_id, "pay_1");
+    let face_value = 1000_0000000i128;
+
+    // Royalty plus the 5% platform fee may not exceed the whole price
+    let res = client.try_set_resale_config(&event_id, &0, &9600);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidResaleConfig)));
+
+    // Without a config tickets resell at face value at most
+    let res = client.try_list_ticket(&payment_id, &(face_value + 1));
+    assert_eq!(res, Err(Ok(TicketPaymentError::ResalePriceTooHigh)));
+    let res = client.try_list_ticket(&payment_id, &0);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPrice)));
+
+    client.list_ticket(&payment_id, &face_value);
+    client.cancel_listing(&payment_id);
+    let buyer = Address::generate(&env);
+    let res = client.try_buy_resale(&payment_id, &buyer, &face_value);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ListingNotFound)));
+
+    // A plain transfer withdraws the listing
+    client.list_ticket(&payment_id, &face_value);
+    client.transfer_ticket(&payment_id, &Address::generate(&env));
+    let res = client.try_buy_resale(&payment_id, &buyer, &face_value);
+    assert_eq!(res, Err(Ok(TicketPaymentError::ListingNotFound)));
+
+    // Pending tickets cannot be listed
+    let pending_buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&pending_buyer, &face_value);
+    token::Client::new(&env, &usdc_id).approve(
+        &pending_buyer,
+        &client.address,
+        &face_value,
+        &99999,
+    );
+    let pending = client.process_payment(
+        &String::from_str(&env, "pay_2"),
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &pending_buyer,
+        &pending_buyer,
+        &usdc_id,
+        &face_value,
+        &1,
+    );
+    let res = client.try_list_ticket(&pending, &face_value);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentStatus)));
+}
+
+#[test]
+fn test_ticket_nft_interface() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let event_id = String::from_str(&env, "event_1");
+    let (payment_id, holder) = buy_confirmed_ticket(&env, &client, &usdc_id, "pay_1");
+
+    assert_eq!(client.owner_of(&payment_id), holder);
+    assert_eq!(client.balance(&holder), 1);
+    assert_eq!(
+        client.token_uri(&payment_id),
+        String::from_str(
+            &env,
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        )
+    );
+
+    // Only the holder can move the ticket directly
+    let next = Address::generate(&env);
+    let res = client.try_transfer(&next, &next, &payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+    client.transfer(&holder, &next, &payment_id);
+    assert_eq!(client.owner_of(&payment_id), next);
+    assert_eq!(client.balance(&holder), 0);
+    assert_eq!(client.balance(&next), 1);
+
+    // An approved operator moves it, paying the transfer fee from the holder
+    let transfer_fee = 100i128;
+    env.as_contract(&client.address, || {
+        set_transfer_fee(&env, event_id.clone(), transfer_fee);
+    });
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&next, &transfer_fee);
+    token::Client::new(&env, &usdc_id).approve(&next, &client.address, &transfer_fee, &99999);
+    let escrow_before = client.get_event_escrow_balance(&event_id, &usdc_id);
+
+    let operator = Address::generate(&env);
+    let last = Address::generate(&env);
+    let res = client.try_transfer_from(&operator, &next, &last, &payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    let live_until = env.ledger().sequence() + 100;
+    client.approve(&next, &operator, &payment_id, &live_until);
+    assert_eq!(client.get_approved(&payment_id), Some(operator.clone()));
+    client.transfer_from(&operator, &next, &last, &payment_id);
+
+    assert_eq!(client.owner_of(&payment_id), last);
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&next), 0);
+    let escrow_after = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(
+        escrow_after.organizer_amount,
+        escrow_before.organizer_amount + transfer_fee
+    );
+
+    // Moving the ticket cleared the approval
+    assert_eq!(client.get_approved(&payment_id), None);
+    let res = client.try_transfer_from(&operator, &last, &next, &payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+}
+
+#[test]
+fn test_ticket_nft_approval_expiry_and_burn() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let (payment_id, holder) = buy_confirmed_ticket(&env, &client, &usdc_id, "pay_1");
+
+    let operator = Address::generate(&env);
+    let res = client.try_approve(&operator, &operator, &payment_id, &1000);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    let live_until = env.ledger().sequence() + 10;
+    client.approve(&holder, &operator, &payment_id, &live_until);
+    env.ledger()
+        .with_mut(|li| li.sequence_number = live_until + 1);
+    assert_eq!(client.get_approved(&payment_id), None);
+    let res = client.try_transfer_from(&operator, &holder, &operator, &payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+
+    // Refunded tickets are burned
+    client.request_guest_refund(&payment_id);
+    let res = client.try_owner_of(&payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentStatus)));
+    assert_eq!(client.balance(&holder), 0);
+}
+
+#[test]
+fn test_gift_purchase_refunds_payer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let payer = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&payer, &(amount * 2));
+    token_client.approve(&payer, &client.address, &(amount * 2), &99999);
+
+    let order_id = client.process_payment(
+        &String::from_str(&env, "gift_1"),
+        &String::from_str(&env, "event_1"),
+        &String::from_str(&env, "tier_1"),
+        &payer,
+        &friend,
+        &usdc_id,
+        &amount,
+        &2,
+    );
+
+    // The payer funded the order; the friend holds the tickets
+    assert_eq!(token_client.balance(&payer), 0);
+    let order = client.get_order(&order_id).unwrap();
+    assert_eq!(order.payer_address, payer);
+    assert_eq!(order.buyer_address, friend);
+    assert_eq!(client.get_buyer_payments(&friend).len(), 2);
+    assert_eq!(client.get_buyer_payments(&payer).len(), 0);
+
+    let ticket_id = order.ticket_ids.get(0).unwrap();
+    let ticket = client.get_payment_status(&ticket_id).unwrap();
+    assert_eq!(ticket.payer_address, payer);
+    assert_eq!(client.owner_of(&ticket_id), friend);
+
+    // Refunds go back to whoever paid, even after the holder passes it on
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+    client.confirm_payment(&ticket_id, &String::from_str(&env, "tx"), &confirmer);
+    client.transfer_ticket(&ticket_id, &Address::generate(&env));
+    client.request_guest_refund(&ticket_id);
+    assert_eq!(token_client.balance(&payer), amount);
+    assert_eq!(token_client.balance(&friend), 0);
+}
+
+#[test]
+fn test_expire_pending_payments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, _organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    let tier_id = String::from_str(&env, "general");
+    let sold = |registry: &::event_registry::EventRegistryClient| {
+        let event = registry.get_event(&event_id).unwrap();
+        event.tiers.get(tier_id.clone()).unwrap().current_sold
+    };
+
+    client.set_confirmation_window(&3600);
+    assert_eq!(client.get_confirmation_window(&event_id), 3600);
+
+    let payer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&payer, &(amount * 3));
+    token_client.approve(&payer, &client.address, &(amount * 3), &99999);
+    client.process_payment(
+        &String::from_str(&env, "order_1"),
+        &event_id,
+        &tier_id,
+        &payer,
+        &payer,
+        &usdc_id,
+        &amount,
+        &3,
+    );
+    assert_eq!(sold(&registry), 3);
+    let first = String::from_str(&env, "order_1-0");
+
+    let res = client.try_expire_payment(&first);
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentNotExpired)));
+
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+
+    // Too late to confirm; anyone may now expire the payment
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+    let res = client.try_confirm_payment(&first, &String::from_str(&env, "tx"), &confirmer);
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentExpired)));
+
+    client.expire_payment(&first);
+    let payment = client.get_payment_status(&first).unwrap();
+    assert_eq!(payment.status, PaymentStatus::Failed);
+    assert_eq!(payment.refunded_amount, amount);
+    assert_eq!(token_client.balance(&payer), amount);
+    assert_eq!(sold(&registry), 2);
+
+    let res = client.try_expire_payment(&first);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidPaymentStatus)));
+
+    // A later purchase is not due yet when the others are swept
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&payer, &amount);
+    token_client.approve(&payer, &client.address, &amount, &99999);
+    client.process_payment(
+        &String::from_str(&env, "order_2"),
+        &event_id,
+        &tier_id,
+        &payer,
+        &payer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+
+    // The sweep reads `limit` payments at a time and resumes where it stopped
+    assert_eq!(client.sweep_expired(&event_id, &2), 1);
+    assert_eq!(client.sweep_expired(&event_id, &10), 1);
+    assert_eq!(client.sweep_expired(&event_id, &10), 0);
+    assert_eq!(token_client.balance(&payer), amount * 3);
+    assert_eq!(sold(&registry), 1);
+
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    assert_eq!(client.sweep_expired(&event_id, &10), 1);
+    assert_eq!(client.sweep_expired(&event_id, &10), 0);
+    assert_eq!(token_client.balance(&payer), amount * 4);
+    assert_eq!(sold(&registry), 0);
+
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, 0);
+    assert_eq!(balance.platform_fee, 0);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_withdrawals_leave_pending_escrow_for_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    client.set_confirmation_window(&3600);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(amount * 2));
+    token_client.approve(&buyer, &client.address, &(amount * 2), &99999);
+    for payment_id in ["pay_1", "pay_2"] {
+        client.process_payment(
+            &String::from_str(&env, payment_id),
+            &event_id,
+            &String::from_str(&env, "general"),
+            &buyer,
+            &buyer,
+            &usdc_id,
+            &amount,
+            &1,
+        );
+    }
+    confirm_tickets(&env, &client, &["pay_1"]);
+
+    // Only the confirmed sale can be withdrawn
+    assert_eq!(
+        client.withdraw_organizer_funds(&event_id, &usdc_id),
+        950_0000000
+    );
+    assert_eq!(client.withdraw_organizer_funds(&event_id, &usdc_id), 0);
+    assert_eq!(token_client.balance(&organizer), 950_0000000);
+
+    // So the unconfirmed one can still be expired and refunded in full
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    client.expire_payment(&String::from_str(&env, "pay_2"));
+    assert_eq!(token_client.balance(&buyer), amount);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, 0);
+    assert_eq!(balance.platform_fee, 50_0000000);
+    assert_eq!(token_client.balance(&client.address), 50_0000000);
+}
+
+#[test]
+fn test_event_confirmation_window_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, _) = setup_test(&env);
+    let event_id = String::from_str(&env, "event_1");
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &amount, &99999);
+    let payment_id = client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &String::from_str(&env, "tier_1"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+
+    // Without any window, pending payments never expire
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+    let res = client.try_expire_payment(&payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentNotExpired)));
+
+    client.set_confirmation_window(&60);
+    client.set_event_confirmation_window(&event_id, &0);
+    assert_eq!(client.get_confirmation_window(&event_id), 0);
+    assert_eq!(client.sweep_expired(&event_id, &10), 0);
+
+    client.set_event_confirmation_window(&event_id, &2_000_000);
+    let res = client.try_expire_payment(&payment_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::PaymentNotExpired)));
+    env.ledger().with_mut(|li| li.timestamp = 2_000_000);
+    client.expire_payment(&payment_id);
+    assert_eq!(token::Client::new(&env, &usdc_id).balance(&buyer), amount);
+}
+
+#[test]
+fn test_settlement_holdback_and_settle_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, platform_wallet, registry_id) = setup_test(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+
+    let res = client.try_set_settlement_config(&10001, &0);
+    assert_eq!(res, Err(Ok(TicketPaymentError::InvalidSettlementConfig)));
+    client.set_settlement_config(&2000, &86400);
+
+    buy_confirmed_ticket(&env, &client, &usdc_id, "pay_1");
+
+    // 20% of the organizer's 950 stays in escrow until settlement
+    assert_eq!(
+        client.withdraw_organizer_funds(&event_id, &usdc_id),
+        760_0000000
+    );
+    assert_eq!(client.withdraw_organizer_funds(&event_id, &usdc_id), 0);
+
+    // Events without an end time only settle once completed
+    let res = client.try_settle_event(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SettlementNotReady)));
+
+    MockEventRegistryClient::new(&env, &registry_id).set_end_time(&1000);
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 86399);
+    let res = client.try_settle_event(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SettlementNotReady)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 86400);
+    let (organizer_amount, platform_fee) = client.settle_event(&event_id, &usdc_id);
+    assert_eq!(organizer_amount, 190_0000000);
+    assert_eq!(platform_fee, 50_0000000);
+    assert_eq!(token_client.balance(&platform_wallet), platform_fee);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, 0);
+    assert_eq!(balance.platform_fee, 0);
+    assert_eq!(balance.total_withdrawn, 950_0000000);
+
+    // Settling again pays nothing
+    assert_eq!(client.settle_event(&event_id, &usdc_id), (0, 0));
+}
+
+#[test]
+fn test_event_without_end_time_settles_when_completed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    client.set_settlement_config(&2000, &86400);
+
+    let buyer = Address::generate(&env);
+    let amount = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &amount);
+    token_client.approve(&buyer, &client.address, &amount, &99999);
+    client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &amount,
+        &1,
+    );
+    confirm_tickets(&env, &client, &["pay_1"]);
+    assert_eq!(
+        client.withdraw_organizer_funds(&event_id, &usdc_id),
+        760_0000000
+    );
+    let res = client.try_settle_event(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SettlementNotReady)));
+
+    // Completing the event starts the dispute window, after which the
+    // holdback is released
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    registry.update_event_status(&event_id, &event_registry::EventStatus::SalesClosed);
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Completed);
+    assert_eq!(registry.get_event(&event_id).unwrap().completed_at, 1000);
+    let res = client.try_settle_event(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SettlementNotReady)));
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 86400);
+    assert_eq!(
+        client.settle_event(&event_id, &usdc_id),
+        (190_0000000, 50_0000000)
+    );
+    assert_eq!(token_client.balance(&organizer), 950_0000000);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_settle_event_rejected_for_cancelled_event() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, usdc_id, _, registry_id) = setup_test(&env);
+    let event_id = String::from_str(&env, "event_1");
+    buy_confirmed_ticket(&env, &client, &usdc_id, "pay_1");
+
+    let registry = MockEventRegistryClient::new(&env, &registry_id);
+    registry.set_end_time(&1000);
+    registry.set_status(&event_registry::EventStatus::Cancelled);
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+
+    let res = client.try_settle_event(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+}
+
+#[test]
+fn test_platform_fee_ledger_and_sweep() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    register_general_event(&env, &registry, "event_2", &organizer, None);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_1 = String::from_str(&env, "event_1");
+    let event_2 = String::from_str(&env, "event_2");
+    let tier_id = String::from_str(&env, "general");
+    let price = 1000_0000000i128;
+    let fee = 50_0000000i128;
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(price * 4));
+    token_client.approve(&buyer, &client.address, &(price * 4), &99999);
+    let buy = |payment_id: &str, event_id: &String, quantity: u32| {
+        client.process_payment(
+            &String::from_str(&env, payment_id),
+            event_id,
+            &tier_id,
+            &buyer,
+            &buyer,
+            &usdc_id,
+            &price,
+            &quantity,
+        )
+    };
+    buy("pay_1", &event_1, 1);
+    buy("pay_2", &event_2, 2);
+    confirm_tickets(&env, &client, &["pay_1", "pay_2-0", "pay_2-1"]);
+
+    let ledger = client.get_platform_fee_ledger(&usdc_id);
+    assert_eq!(ledger.accrued, fee * 3);
+    assert_eq!(ledger.pending, fee * 3);
+
+    // Refunds hand the fee back, so it is no longer accrued
+    client.request_guest_refund(&String::from_str(&env, "pay_2-0"));
+    let ledger = client.get_platform_fee_ledger(&usdc_id);
+    assert_eq!(ledger.accrued, fee * 2);
+    assert_eq!(ledger.pending, fee * 2);
+
+    // Fees stay in escrow until their event settles
+    let res = client.try_withdraw_platform_fees(&event_1, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SettlementNotReady)));
+    assert_eq!(
+        client.withdraw_all_platform_fees(&usdc_id, &0, &10).amount,
+        0
+    );
+
+    for event_id in [&event_1, &event_2] {
+        registry.update_event_status(event_id, &event_registry::EventStatus::SalesClosed);
+        registry.update_event_status(event_id, &event_registry::EventStatus::Completed);
+    }
+    assert_eq!(client.withdraw_platform_fees(&event_1, &usdc_id), fee);
+    let ledger = client.get_platform_fee_ledger(&usdc_id);
+    assert_eq!(ledger.withdrawn, fee);
+    assert_eq!(ledger.pending, fee);
+
+    // The sweep collects what is left, a page of events at a time
+    let page = client.withdraw_all_platform_fees(&usdc_id, &0, &1);
+    assert_eq!(page.amount, 0);
+    assert_eq!(page.next_index, 1);
+    assert!(!page.complete);
+    let page = client.withdraw_all_platform_fees(&usdc_id, &page.next_index, &1);
+    assert_eq!(page.amount, fee);
+    assert!(page.complete);
+    assert_eq!(
+        client.withdraw_all_platform_fees(&usdc_id, &0, &10).amount,
+        0
+    );
+    let ledger = client.get_platform_fee_ledger(&usdc_id);
+    assert_eq!(ledger.accrued, fee * 2);
+    assert_eq!(ledger.withdrawn, fee * 2);
+    assert_eq!(ledger.pending, 0);
+    assert_eq!(
+        client
+            .get_event_escrow_balance(&event_2, &usdc_id)
+            .platform_fee,
+        0
+    );
+}
+
+#[test]
+fn test_fee_sweep_leaves_refunds_of_unsettled_events_covered() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    let price = 1000_0000000i128;
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(price * 3));
+    token_client.approve(&buyer, &client.address, &(price * 3), &99999);
+    client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &price,
+        &3,
+    );
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+    for index in ["pay_1-0", "pay_1-1", "pay_1-2"] {
+        client.confirm_payment(
+            &String::from_str(&env, index),
+            &String::from_str(&env, "tx"),
+            &confirmer,
+        );
+    }
+
+    // A routine sweep takes nothing from an event that has not settled
+    client.request_guest_refund(&String::from_str(&env, "pay_1-0"));
+    assert_eq!(
+        client.withdraw_all_platform_fees(&usdc_id, &0, &10).amount,
+        0
+    );
+
+    // So cancelling it later still refunds everyone in full
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Cancelled);
+    assert_eq!(client.trigger_bulk_refund(&event_id, &10, &organizer), 2);
+    assert_eq!(token_client.balance(&buyer), price * 3);
+    assert_eq!(token_client.balance(&client.address), 0);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, 0);
+    assert_eq!(balance.platform_fee, 0);
+}
+
+#[test]
+fn test_revenue_split_withdrawals_and_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let promoter = Address::generate(&env);
+    let venue = Address::generate(&env);
+    let artist = Address::generate(&env);
+    let split = |payee: &Address, bps: u32| event_registry::RevenueSplit {
+        payee: payee.clone(),
+        bps,
+    };
+    register_general_event(
+        &env,
+        &registry,
+        "festival",
+        &organizer,
+        Some(soroban_sdk::vec![
+            &env,
+            split(&promoter, 6000),
+            split(&venue, 3000),
+            split(&artist, 1000),
+        ]),
+    );
+    let event_id = String::from_str(&env, "festival");
+
+    let buyer = Address::generate(&env);
+    let price = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(price * 2));
+    token_client.approve(&buyer, &client.address, &(price * 2), &99999);
+    client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &price,
+        &1,
+    );
+    confirm_tickets(&env, &client, &["pay_1"]);
+
+    // The artist claims their own 10% of the organizer's 950
+    let res = client.try_claim_revenue_share(&event_id, &usdc_id, &organizer);
+    assert_eq!(res, Err(Ok(TicketPaymentError::Unauthorized)));
+    assert_eq!(
+        client.claim_revenue_share(&event_id, &usdc_id, &artist),
+        95_0000000
+    );
+    assert_eq!(client.claim_revenue_share(&event_id, &usdc_id, &artist), 0);
+
+    // An organizer withdrawal pays everyone else their share
+    assert_eq!(
+        client.withdraw_organizer_funds(&event_id, &usdc_id),
+        855_0000000
+    );
+    assert_eq!(token_client.balance(&promoter), 570_0000000);
+    assert_eq!(token_client.balance(&venue), 285_0000000);
+    assert_eq!(token_client.balance(&artist), 95_0000000);
+    assert_eq!(token_client.balance(&organizer), 0);
+
+    // New sales are shared the same way
+    client.process_payment(
+        &String::from_str(&env, "pay_2"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &price,
+        &1,
+    );
+    confirm_tickets(&env, &client, &["pay_2"]);
+    assert_eq!(
+        client.claim_revenue_share(&event_id, &usdc_id, &venue),
+        285_0000000
+    );
+    assert_eq!(
+        client.get_revenue_claimed(&event_id, &usdc_id, &venue),
+        570_0000000
+    );
+    assert_eq!(
+        client
+            .get_event_escrow_balance(&event_id, &usdc_id)
+            .organizer_amount,
+        950_0000000 - 285_0000000
+    );
+}
+
+#[test]
+fn test_payment_keeps_fee_rate_of_purchase() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, _) = setup_with_registry(&env);
+    let event_id = String::from_str(&env, "event_1");
+    let buyer = Address::generate(&env);
+    let price = 1000_0000000i128;
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(price * 2));
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &(price * 2), &99999);
+    let buy = |payment_id: &str| {
+        client.process_payment(
+            &String::from_str(&env, payment_id),
+            &event_id,
+            &String::from_str(&env, "general"),
+            &buyer,
+            &buyer,
+            &usdc_id,
+            &price,
+            &1,
+        )
+    };
+
+    let first = buy("pay_1");
+    registry.set_event_fee(&event_id, &0);
+    let second = buy("pay_2");
+
+    let first = client.get_payment_status(&first).unwrap();
+    assert_eq!(first.platform_fee_bps, 500);
+    assert_eq!(first.platform_fee, 50_0000000);
+    let second = client.get_payment_status(&second).unwrap();
+    assert_eq!(second.platform_fee_bps, 0);
+    assert_eq!(second.platform_fee, 0);
+    assert_eq!(
+        client
+            .get_event_escrow_balance(&event_id, &usdc_id)
+            .platform_fee,
+        50_0000000
+    );
+}
+
+#[test]
+fn test_buyer_pays_fee_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, usdc_id, _) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    let tier_id = String::from_str(&env, "general");
+    let price = 1000_0000000i128;
+    let fee = 50_0000000i128;
+
+    assert_eq!(client.get_fee_mode(&event_id), FeeMode::OrganizerAbsorbs);
+    assert_eq!(client.get_ticket_price(&event_id, &tier_id), price);
+    client.set_fee_mode(&event_id, &FeeMode::BuyerPays);
+    assert_eq!(client.get_ticket_price(&event_id, &tier_id), price + fee);
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &((price + fee) * 2));
+    let buy = |payment_id: &str, amount: i128| {
+        client.try_process_payment(
+            &String::from_str(&env, payment_id),
+            &event_id,
+            &tier_id,
+            &buyer,
+            &buyer,
+            &usdc_id,
+            &amount,
+            &2,
+        )
+    };
+
+    // The tier price alone no longer covers a ticket, nor does an allowance for it
+    assert_eq!(
+        buy("pay_1", price),
+        Err(Ok(TicketPaymentError::InvalidPrice))
+    );
+    token_client.approve(&buyer, &client.address, &(price * 2), &99999);
+    assert_eq!(
+        buy("pay_1", price + fee),
+        Err(Ok(TicketPaymentError::InsufficientAllowance))
+    );
+
+    token_client.approve(&buyer, &client.address, &((price + fee) * 2), &99999);
+    buy("pay_1", price + fee).unwrap().unwrap();
+    assert_eq!(token_client.balance(&buyer), 0);
+
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, price * 2);
+    assert_eq!(balance.platform_fee, fee * 2);
+
+    let ticket_id = String::from_str(&env, "pay_1-0");
+    let payment = client.get_payment_status(&ticket_id).unwrap();
+    assert_eq!(payment.amount, price + fee);
+    assert_eq!(payment.organizer_amount, price);
+    assert_eq!(payment.platform_fee, fee);
+
+    // A refund returns everything the payer was charged, fee included
+    client.request_guest_refund(&ticket_id);
+    assert_eq!(token_client.balance(&buyer), price + fee);
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, price);
+    assert_eq!(balance.platform_fee, fee);
+    assert_eq!(client.get_platform_fee_ledger(&usdc_id).pending, fee);
+
+    // Switching back only affects later sales
+    client.set_fee_mode(&event_id, &FeeMode::OrganizerAbsorbs);
+    let payment = client
+        .get_payment_status(&String::from_str(&env, "pay_1-1"))
+        .unwrap();
+    assert_eq!(payment.organizer_amount, price);
+    confirm_tickets(&env, &client, &["pay_1-1"]);
+    assert_eq!(client.withdraw_organizer_funds(&event_id, &usdc_id), price);
+}
+
+#[test]
+fn test_multi_ticket_amounts_add_up_to_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let event_id = String::from_str(&env, "event_1");
+    let tier_id = String::from_str(&env, "general");
+    // 5% of 3 x 333 is 49.95, which leaves a remainder when split per ticket
+    registry.update_tier(&event_id, &tier_id, &333, &10);
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &999);
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &999, &99999);
+    client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &333,
+        &3,
+    );
+
+    for (ticket, fee) in [("pay_1-0", 17), ("pay_1-1", 16), ("pay_1-2", 16)] {
+        let payment = client
+            .get_payment_status(&String::from_str(&env, ticket))
+            .unwrap();
+        assert_eq!(payment.platform_fee, fee);
+        assert_eq!(payment.organizer_amount, 333 - fee);
+    }
+
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, 950);
+    assert_eq!(balance.platform_fee, 49);
+    // Reconciling a page at a time carries the totals over
+    let zero = SalesEscrow {
+        organizer_amount: 0,
+        platform_fee: 0,
+    };
+    let page = client.reconcile_event(&event_id, &usdc_id, &0, &2, &zero);
+    assert!(!page.complete);
+    assert!(!page.balanced);
+    assert_eq!(page.next_index, 2);
+    let reconciliation =
+        client.reconcile_event(&event_id, &usdc_id, &page.next_index, &2, &page.tickets);
+    assert!(reconciliation.complete);
+    assert!(reconciliation.balanced);
+    assert_eq!(
+        reconciliation.tickets,
+        SalesEscrow {
+            organizer_amount: 950,
+            platform_fee: 49,
+        }
+    );
+
+    // Refunding every ticket empties the escrow exactly
+    client.request_guest_refund(&String::from_str(&env, "pay_1-0"));
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+    for ticket in ["pay_1-1", "pay_1-2"] {
+        client.confirm_payment(
+            &String::from_str(&env, ticket),
+            &String::from_str(&env, "tx"),
+            &confirmer,
+        );
+    }
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Cancelled);
+    assert_eq!(client.trigger_bulk_refund(&event_id, &10, &organizer), 2);
+
+    let balance = client.get_event_escrow_balance(&event_id, &usdc_id);
+    assert_eq!(balance.organizer_amount, 0);
+    assert_eq!(balance.platform_fee, 0);
+    let reconciliation = client.reconcile_event(&event_id, &usdc_id, &0, &10, &zero);
+    assert!(reconciliation.balanced);
+    assert_eq!(reconciliation.escrow.organizer_amount, 0);
+    assert_eq!(reconciliation.escrow.platform_fee, 0);
+}
+
+#[test]
+fn test_minimum_sales_target_locks_withdrawals_and_refunds_when_missed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let tier_id = String::from_str(&env, "general");
+    let price = 1000_0000000i128;
+    let met = register_sales_target_event(&env, &registry, "met", &organizer, 2, 1000);
+    let missed = register_sales_target_event(&env, &registry, "missed", &organizer, 3, 1000);
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(price * 4));
+    token_client.approve(&buyer, &client.address, &(price * 4), &99999);
+    let buy = |payment_id: &str, event_id: &String, quantity: u32| {
+        client.process_payment(
+            &String::from_str(&env, payment_id),
+            event_id,
+            &tier_id,
+            &buyer,
+            &buyer,
+            &usdc_id,
+            &price,
+            &quantity,
+        );
+    };
+    buy("pay_1", &met, 1);
+    buy("pay_2", &missed, 2);
+
+    // Nothing leaves escrow until the target is met
+    let res = client.try_withdraw_organizer_funds(&met, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SalesTargetNotMet)));
+    let res = client.try_withdraw_platform_fees(&met, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SalesTargetNotMet)));
+    let res = client.try_request_guest_refund(&String::from_str(&env, "pay_2-0"));
+    assert_eq!(res, Err(Ok(TicketPaymentError::TicketNotRefundable)));
+
+    // Reaching the target only counts once the deadline has passed
+    buy("pay_3", &met, 1);
+    confirm_tickets(&env, &client, &["pay_1", "pay_3"]);
+    let res = client.try_withdraw_organizer_funds(&met, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SalesTargetNotMet)));
+
+    // Once the deadline passes, the met event pays out and the missed event
+    // refunds in full without the organizer
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(
+        client.withdraw_organizer_funds(&met, &usdc_id),
+        2 * 950_0000000
+    );
+    let res = client.try_withdraw_organizer_funds(&missed, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+
+    let before = token_client.balance(&buyer);
+    client.request_guest_refund(&String::from_str(&env, "pay_2-0"));
+    client.request_guest_refund(&String::from_str(&env, "pay_2-1"));
+    assert_eq!(token_client.balance(&buyer), before + price * 2);
+    assert_eq!(
+        client
+            .get_event_escrow_balance(&missed, &usdc_id)
+            .organizer_amount,
+        0
+    );
+}
+
+#[test]
+fn test_expired_sales_do_not_meet_sales_target() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    client.set_confirmation_window(&100);
+    let tier_id = String::from_str(&env, "general");
+    let price = 1000_0000000i128;
+    let event_id =
+        register_sales_target_event(&env, &registry, "target_event", &organizer, 2, 1000);
+
+    // Two unconfirmed tickets push the count up to the target...
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(price * 2));
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &(price * 2), &99999);
+    client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &tier_id,
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &price,
+        &2,
+    );
+    assert_eq!(registry.get_event(&event_id).unwrap().current_supply, 2);
+    let res = client.try_withdraw_organizer_funds(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::SalesTargetNotMet)));
+
+    // ...but expire before the deadline, so the target is missed
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.sweep_expired(&event_id, &10), 2);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let event = registry.get_event(&event_id).unwrap();
+    assert!(!event.sales_target_met);
+    assert!(event.sales_target_missed(1000));
+    let res = client.try_withdraw_organizer_funds(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+}
+
+#[test]
+fn test_audit_solvency_pages_through_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    register_general_event(&env, &registry, "event_2", &organizer, None);
+    let price = 1000_0000000i128;
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc_id).mint(&buyer, &(price * 3));
+    token::Client::new(&env, &usdc_id).approve(&buyer, &client.address, &(price * 3), &99999);
+    for (payment_id, event_id, quantity) in [("pay_1", "event_1", 1u32), ("pay_2", "event_2", 2)] {
+        client.process_payment(
+            &String::from_str(&env, payment_id),
+            &String::from_str(&env, event_id),
+            &String::from_str(&env, "general"),
+            &buyer,
+            &buyer,
+            &usdc_id,
+            &price,
+            &quantity,
+        );
+    }
+
+    let page = client.audit_solvency(&usdc_id, &1);
+    assert_eq!(page.liabilities, price);
+    assert_eq!(page.next_index, 1);
+    assert!(!page.complete);
+    assert!(!page.solvent);
+
+    // The next call carries on from the stored cursor and total
+    let report = client.audit_solvency(&usdc_id, &1);
+    assert!(report.complete);
+    assert!(report.solvent);
+    assert_eq!(report.liabilities, price * 3);
+    assert_eq!(report.balance, price * 3);
+
+    // A finished audit starts over from the first event
+    let report = client.audit_solvency(&usdc_id, &10);
+    assert!(report.complete);
+    assert_eq!(report.liabilities, price * 3);
+}
+
+#[test]
+fn test_cancelled_event_releases_leftover_escrow_after_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, registry, usdc_id, organizer) = setup_with_registry(&env);
+    let token_client = token::Client::new(&env, &usdc_id);
+    let event_id = String::from_str(&env, "event_1");
+    let price = 1000_0000000i128;
+    client.set_transfer_fee(&event_id, &10_0000000);
+    client.set_resale_config(&event_id, &2000, &1000);
+
+    let buyer = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let reseller_buyer = Address::generate(&env);
+    for (account, funds) in [(&buyer, price * 3), (&reseller_buyer, 1100_0000000)] {
+        token::StellarAssetClient::new(&env, &usdc_id).mint(account, &funds);
+        token_client.approve(account, &client.address, &funds, &99999);
+    }
+    client.process_payment(
+        &String::from_str(&env, "pay_1"),
+        &event_id,
+        &String::from_str(&env, "general"),
+        &buyer,
+        &buyer,
+        &usdc_id,
+        &price,
+        &2,
+    );
+    let first = String::from_str(&env, "pay_1-0");
+    let second = String::from_str(&env, "pay_1-1");
+    let confirmer = Address::generate(&env);
+    client.add_confirmer(&confirmer);
+    for ticket in [&first, &second] {
+        client.confirm_payment(ticket, &String::from_str(&env, "tx"), &confirmer);
+    }
+
+    // A transfer fee and a resale leave earnings in escrow beyond the tickets
+    client.transfer_ticket(&second, &friend);
+    client.list_ticket(&first, &1100_0000000);
+    client.buy_resale(&first, &reseller_buyer, &1100_0000000);
+
+    registry.update_event_status(&event_id, &event_registry::EventStatus::Cancelled);
+
+    // Nothing is released while a ticket still awaits its refund
+    let res = client.try_settle_event(&event_id, &usdc_id);
+    assert_eq!(res, Err(Ok(TicketPaymentError::EventCancelled)));
+    assert_eq!(client.trigger_bulk_refund(&event_id, &10, &organizer), 2);
+    assert_eq!(token_client.balance(&reseller_buyer), price);
+
+    // The transfer fee and royalty go to the organizer, the resale fee to the platform
+    assert_eq!(
+        client.settle_event(&event_id, &usdc_id),
+        (10_0000000 + 110_0000000, 55_0000000)
+    );
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert!(client.audit_solvency(&usdc_id, &10).solvent);
+}
@@ -1,28 +1,45 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, String, Vec};
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum PaymentStatus {
-    Pending,
-    Confirmed,
-    Refunded,
-    Failed,
-}
+pub use agora_types::PaymentStatus;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Payment {
     pub payment_id: String,
+    pub order_id: String, // parent Order this ticket was bought in
     pub event_id: String,
-    pub buyer_address: Address,
+    pub payer_address: Address, // funded the ticket; refunds are paid to it
+    pub buyer_address: Address, // current ticket holder
     pub ticket_tier_id: String,
-    pub amount: i128, // USDC amount in stroops
+    pub token_address: Address, // token the ticket was paid in; refunds settle in it
+    pub amount: i128,           // amount in stroops of `token_address`
     pub platform_fee: i128,
+    pub platform_fee_bps: u32, // fee rate in effect at purchase; later changes don't apply
     pub organizer_amount: i128,
+    pub refunded_amount: i128, // amount returned to the buyer, if refunded
+    pub refunded_fee: i128,    // part of `refunded_amount` the platform gave back from its fee
     pub status: PaymentStatus,
     pub transaction_hash: String,
     pub created_at: u64,
     pub confirmed_at: Option<u64>,
+    pub checked_in_at: Option<u64>,
+}
+
+/// A single `process_payment` call, linking the tickets it created.
+///
+/// The order is keyed by the caller's `payment_id`. A single ticket reuses that
+/// ID; a batch names its tickets `<payment_id>-<index>`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Order {
+    pub order_id: String,
+    pub event_id: String,
+    pub payer_address: Address,
+    pub buyer_address: Address,
+    pub ticket_tier_id: String,
+    pub ticket_ids: Vec<String>,
+    pub total_amount: i128,
+    pub created_at: u64,
 }
 
 #[contracttype]
@@ -33,18 +50,160 @@ pub struct EventBalance {
     pub platform_fee: i128,
 }
 
+/// Organizer rules for reselling an event's tickets.
+///
+/// Both values are in basis points (10000 = 100%): `max_markup_bps` caps a
+/// listing at the ticket's face value plus that markup, and `royalty_bps` of
+/// every resale goes to the organizer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResaleConfig {
+    pub max_markup_bps: u32,
+    pub royalty_bps: u32,
+}
+
+/// Who bears an event's platform fee on primary sales.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeeMode {
+    /// The fee comes out of the tier price; the buyer pays the tier price
+    OrganizerAbsorbs,
+    /// The fee is added on top; the organizer receives the full tier price
+    BuyerPays,
+}
+
+/// A ticket offered for resale by its current holder.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub payment_id: String,
+    pub seller: Address,
+    pub price: i128, // asking price in the ticket's `token_address`
+    pub listed_at: u64,
+}
+
+/// Running platform fee totals for one token across every event.
+///
+/// `accrued` counts fees earned, net of fees returned with refunds; `pending`
+/// is the part of it still held in escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeeLedger {
+    pub accrued: i128,
+    pub withdrawn: i128,
+    pub pending: i128,
+}
+
+/// Platform fees swept from one page of the events holding escrow in a token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeeSweep {
+    pub amount: i128,    // fees sent to the platform wallet
+    pub next_index: u32, // index of the first event not read yet; `start` of the next page
+    pub complete: bool,  // every event holding escrow in the token has been read
+}
+
+/// Escrow held for an event's primary ticket sales in one token, net of
+/// refunds.
+///
+/// Unlike `EventBalance` it leaves out transfer fees, resale proceeds and
+/// withdrawals, so it always equals the sum of the event's `Payment` records.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalesEscrow {
+    pub organizer_amount: i128,
+    pub platform_fee: i128,
+}
+
+/// An event's per-ticket totals next to the escrow its sales and refunds
+/// actually moved.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowReconciliation {
+    pub tickets: SalesEscrow, // summed from the event's Payment records read so far
+    pub escrow: SalesEscrow,  // recorded as sales and refunds moved escrow
+    pub next_index: u32,      // index of the first payment not read yet
+    pub complete: bool,       // every payment of the event has been read
+    pub balanced: bool,
+}
+
+/// The contract's holdings of one token against the escrow it owes in it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolvencyReport {
+    pub liabilities: i128, // organizer amounts plus platform fees of the events read so far
+    pub balance: i128,     // the contract's balance of the token
+    pub next_index: u32,   // index of the first event not read yet
+    pub complete: bool,    // every event holding escrow in the token has been read
+    pub solvent: bool,
+}
+
+/// Progress of a paged `audit_solvency` through the events holding escrow in
+/// one token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolvencyAudit {
+    pub next_index: u32,   // index of the first event not read yet
+    pub liabilities: i128, // escrow of the events read so far
+}
+
+/// How organizer revenue is released around the end of an event.
+///
+/// `holdback_bps` of the revenue (10000 = 100%) stays in escrow until
+/// `dispute_window` seconds after the event's `end_time`, or after it was
+/// completed if it has none; then the event can be settled and everything is
+/// released.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementConfig {
+    pub holdback_bps: u32,
+    pub dispute_window: u64,
+}
+
+/// An address allowed to move one ticket on its holder's behalf.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TicketApproval {
+    pub approved: Address,
+    pub live_until_ledger: u32, // last ledger sequence the approval is valid for
+}
+
 #[contracttype]
 pub enum DataKey {
-    Payment(String),         // payment_id -> Payment
-    EventPayments(String),   // event_id -> Vec<payment_id>
-    BuyerPayments(Address),  // buyer_address -> Vec<payment_id>
-    Admin,                   // Contract administrator address
-    UsdcToken,               // USDC token address
-    PlatformWallet,          // Platform wallet address
-    EventRegistry,           // Event Registry contract address
-    Initialized,             // Initialization flag
-    TokenWhitelist(Address), // token_address -> bool
-    Balances(String),        // event_id -> EventBalance (escrow tracking)
-    TransferFee(String),     // event_id -> transfer_fee amount
-    BulkRefundIndex(String), // event_id -> last processed payment index
+    Payment(String),            // payment_id -> Payment
+    EventPayments(String),      // event_id -> Vec<payment_id>
+    BuyerPayments(Address),     // buyer_address -> Vec<payment_id>
+    Admin,                      // Contract administrator address
+    UsdcToken,                  // USDC token address
+    PlatformWallet,             // Platform wallet address
+    EventRegistry,              // Event Registry contract address
+    Initialized,                // Initialization flag
+    TokenWhitelist(Address),    // token_address -> bool
+    Balances(String, Address),  // (event_id, token) -> EventBalance (escrow tracking)
+    TransferFee(String),        // event_id -> transfer_fee amount
+    BulkRefundIndex(String),    // event_id -> last processed payment index
+    PricePhase(String, String), // (event_id, tier_id) -> last announced price phase index
+    Order(String),              // order_id -> Order
+    Confirmer(Address),         // confirmer_address -> bool
+    ResaleConfig(String),       // event_id -> ResaleConfig
+    FeeMode(String),            // event_id -> FeeMode
+    Listing(String),            // payment_id -> Listing
+    Approval(String),           // payment_id -> TicketApproval
+    DefaultConfirmWindow,       // seconds a payment may stay Pending, unless overridden
+    ConfirmWindow(String),      // event_id -> seconds, overriding the default
+    SettlementConfig,           // SettlementConfig for every event
+    PlatformFees(Address),      // token -> PlatformFeeLedger
+    EscrowEvents(Address),      // token -> Vec<event_id> that have held escrow in it
+    // (event_id, token, payee) -> revenue claimed by a payee of a split event
+    RevenueClaimed(String, Address, Address),
+    // (event_id, token) -> SalesEscrow of the event's primary sales
+    SalesEscrow(String, Address),
+    // (event_id, token) -> SalesEscrow of the event's payments still Pending
+    PendingEscrow(String, Address),
+    // event_id -> index of the first payment `sweep_expired` has not passed
+    ExpirySweepIndex(String),
+    // event_id -> tickets of the event that are neither refunded nor failed
+    LiveTickets(String),
+    // token -> SolvencyAudit in progress
+    SolvencyAudit(Address),
 }
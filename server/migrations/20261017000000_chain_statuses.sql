@@ -0,0 +1,19 @@
+-- Ticket and transaction statuses now use the payment statuses reported by the
+-- ticket_payment contract (see agora_types::PaymentStatus).
+UPDATE tickets SET status = CASE status
+    WHEN 'active' THEN 'confirmed'
+    WHEN 'used' THEN 'checked_in'
+    WHEN 'cancelled' THEN 'refunded'
+    ELSE status
+END;
+
+UPDATE transactions SET status = CASE status
+    WHEN 'completed' THEN 'confirmed'
+    ELSE status
+END;
+
+ALTER TABLE tickets ADD CONSTRAINT tickets_status_check
+    CHECK (status IN ('pending', 'confirmed', 'refunded', 'failed', 'checked_in'));
+
+ALTER TABLE transactions ADD CONSTRAINT transactions_status_check
+    CHECK (status IN ('pending', 'confirmed', 'refunded', 'failed', 'checked_in'));
@@ -0,0 +1,4 @@
+//! Statuses mirrored from the Soroban contracts, so API payloads use the same
+//! variants the chain reports. Database columns store their snake_case names.
+
+pub use agora_types::{EventStatus, PaymentStatus};
@@ -1,3 +1,4 @@
+pub mod chain;
 pub mod event;
 pub mod organizer;
 pub mod ticket;
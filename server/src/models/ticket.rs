@@ -1,3 +1,4 @@
+use super::chain::PaymentStatus;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -24,7 +25,8 @@ pub struct Ticket {
     pub id: Uuid,
     pub user_id: Uuid,
     pub ticket_tier_id: Uuid,
-    pub status: String,
+    #[sqlx(try_from = "String")]
+    pub status: PaymentStatus,
     pub qr_code: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,